@@ -8,13 +8,12 @@ use core::view_plugins::{ViewHandle, ViewPlugins, ViewInstance};
 use core::backend_plugin::{BackendPlugins};
 use core::session::{Sessions, Session, SessionHandle};
 use core::reader_wrapper::ReaderWrapper;
-use self::viewdock::{Workspace, Rect, Direction, DockHandle, DragTarget, DropTarget, Dock};
+use self::viewdock::{Workspace, Rect, Direction, Edge, DockHandle, DragTarget, DropTarget, Dock, LayoutHistory};
 use menu::*;
 use imgui_sys::Imgui;
 use prodbg_api::ui_ffi::{PDVec2, ImguiKey};
 use prodbg_api::view::CViewCallbacks;
 use std::os::raw::{c_void, c_int};
-use std::collections::VecDeque;
 //use std::mem::transmute;
 
 const WIDTH: usize = 1280;
@@ -51,8 +50,7 @@ pub struct Window {
 
     ///
     pub ws: Workspace,
-    ws_states: VecDeque<String>,
-    cur_state_index: usize,
+    ws_history: LayoutHistory,
 
     pub mouse_state: MouseState,
 
@@ -119,8 +117,7 @@ impl Windows {
                             width as c_int,
                             height as c_int);
         let ws = Workspace::new(Rect::new(0.0, 0.0, width as f32, (height - 20) as f32)).unwrap();
-        let mut ws_states = VecDeque::with_capacity(WORKSPACE_UNDO_LIMIT);
-        ws_states.push_back(ws.save_state());
+        let ws_history = LayoutHistory::new(ws.save_state(), WORKSPACE_UNDO_LIMIT);
         return Ok(Window {
             win: win,
             menu: Menu::new(),
@@ -128,8 +125,7 @@ impl Windows {
             menu_id_offset: 1000,
             mouse_state: MouseState::new(),
             ws: ws,
-            ws_states: ws_states,
-            cur_state_index: 0usize,
+            ws_history: ws_history,
         });
     }
 
@@ -275,9 +271,11 @@ impl Window {
                         DragTarget::SplitSizer(_, _, direction) => match direction {
                             Direction::Vertical => CursorStyle::ResizeLeftRight,
                             Direction::Horizontal => CursorStyle::ResizeUpDown
-                        }
+                        },
+                        DragTarget::RootEdge(edge) => Self::get_root_edge_cursor(edge),
                     };
                     if self.win.get_mouse_down(MouseButton::Left) {
+                        self.ws.begin_drag(target);
                         next_state = Some(State::Dragging(target, self.ws.save_state()));
                     }
                 } else {
@@ -305,6 +303,21 @@ impl Window {
                     let delta = (pm.0 - mouse_pos.0, pm.1 - mouse_pos.1);
                     self.ws.drag_sizer(handle, index, delta);
                 } else {
+                    self.ws.end_drag();
+                    next_state = Some(State::Default);
+                    cursor = CursorStyle::Arrow;
+                    ws_state_to_save = Some(ws_state.clone());
+                }
+            },
+
+            State::Dragging(DragTarget::RootEdge(edge), ref ws_state) => {
+                if self.win.get_mouse_down(MouseButton::Left) {
+                    cursor = Self::get_root_edge_cursor(edge);
+                    let pm = self.mouse_state.prev_mouse;
+                    let delta = (pm.0 - mouse_pos.0, pm.1 - mouse_pos.1);
+                    self.ws.drag_root_edge(edge, delta);
+                } else {
+                    self.ws.end_drag();
                     next_state = Some(State::Default);
                     cursor = CursorStyle::Arrow;
                     ws_state_to_save = Some(ws_state.clone());
@@ -320,12 +333,12 @@ impl Window {
                         _ => CursorStyle::ClosedHand,
                     }
                 } else {
-                    if let Some(DropTarget::Dock(target)) = drop_target {
-                        if target != handle {
+                    if let Some(ref target) = drop_target {
+                        if self.ws.commit_drop(handle, target).is_ok() {
                             ws_state_to_save = Some(ws_state.clone());
-                            self.ws.swap_docks(handle, target);
                         }
                     }
+                    self.ws.end_drag();
                     next_state = Some(State::Default);
                     cursor = CursorStyle::Arrow;
                 }
@@ -342,6 +355,13 @@ impl Window {
         }
     }
 
+    fn get_root_edge_cursor(edge: Edge) -> CursorStyle {
+        match edge {
+            Edge::Left | Edge::Right => CursorStyle::ResizeLeftRight,
+            Edge::Top | Edge::Bottom => CursorStyle::ResizeUpDown,
+        }
+    }
+
     fn update_key_state(&mut self) {
         Imgui::clear_keys();
 
@@ -473,7 +493,7 @@ impl Window {
     }
 
     fn restore_workspace_state(&mut self, view_plugins: &mut ViewPlugins) {
-        self.ws = Workspace::from_state(&self.ws_states[self.cur_state_index]);
+        self.ws = Workspace::from_state(self.ws_history.current());
         let win_size = self.win.get_size();
         self.ws.update(Rect::new(0.0, 0.0, win_size.0 as f32, win_size.1 as f32));
         let docks = self.ws.get_docks();
@@ -504,15 +524,13 @@ impl Window {
     }
 
     fn undo_workspace_change(&mut self, view_plugins: &mut ViewPlugins) {
-        if self.cur_state_index > 0 {
-            self.cur_state_index -= 1;
+        if self.ws_history.undo().is_some() {
             self.restore_workspace_state(view_plugins);
         }
     }
 
     fn redo_workspace_change(&mut self, view_plugins: &mut ViewPlugins) {
-        if self.cur_state_index < self.ws_states.len() - 1 {
-            self.cur_state_index += 1;
+        if self.ws_history.redo().is_some() {
             self.restore_workspace_state(view_plugins);
         }
     }
@@ -523,13 +541,7 @@ impl Window {
     }
 
     fn save_workspace_state(&mut self, state: String) {
-        self.ws_states.drain(self.cur_state_index+1..);
-        if self.cur_state_index == WORKSPACE_UNDO_LIMIT - 1 {
-            self.ws_states.pop_front();
-            self.cur_state_index -= 1;
-        }
-        self.ws_states.push_back(state);
-        self.cur_state_index += 1;
+        self.ws_history.push(state);
     }
 
     fn split_view(&mut self, name: &String, view_plugins: &mut ViewPlugins, pos: (f32, f32), direction: Direction) {
@@ -1,8 +1,18 @@
 mod serialize;
 
 use dock::{Dock, DockHandle};
-use rect::Rect;
-use super::{DragTarget, DropTarget};
+use rect::{Rect, Direction};
+use super::{Area, Split, SplitHandle, DragTarget, DropTarget, SplitStyle};
+
+/// Height of the header (title/tab strip) of a container
+pub const HEADER_HEIGHT: f32 = 30.0;
+
+/// Side of a container where the header (tab strip) is placed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TabPosition {
+    Top,
+    Bottom,
+}
 
 /// Holds a list of available docks
 #[derive(Debug, Clone)]
@@ -11,13 +21,39 @@ pub struct Container {
     /// to implement tabs but only one dock should be visible at a time
     pub docks: Vec<Dock>,
     pub rect: Rect,
+    /// Index of the dock (tab) that is currently shown
+    pub active_dock: usize,
+    /// If false other docks can't be dropped on this container
+    pub accepts_drops: bool,
+    /// Minimum (width, height) of this container in pixels
+    pub min_size: (f32, f32),
+    /// (width, height) in pixels this container should get from `Split::fit_all_to_preferred`
+    pub preferred_size: Option<(f32, f32)>,
+    /// Rendering hints. Doesn't affect the layout
+    pub style: Option<SplitStyle>,
+    /// Fixed containers keep their size when the parent split is resized as long as there are
+    /// flexible siblings to take the change
+    pub fixed: bool,
+    /// Where the tab strip is placed
+    pub tab_position: TabPosition,
+    /// Drawn after its siblings (see `Area::visible_docks`), e.g. during a transient highlight.
+    /// Doesn't affect the layout. Not serialized.
+    pub elevated: bool,
 }
 
 impl Container {
     pub fn new(dock: Dock, rect: Rect) -> Container {
         Container {
             docks: vec!(dock),
-            rect: rect,
+            rect,
+            active_dock: 0,
+            accepts_drops: true,
+            min_size: (0.0, 0.0),
+            preferred_size: None,
+            style: None,
+            fixed: false,
+            tab_position: TabPosition::Top,
+            elevated: false,
         }
     }
 
@@ -25,21 +61,130 @@ impl Container {
         self.docks.iter().find(|&dock| dock.handle == handle)
     }
 
+    pub fn find_dock_mut(&mut self, handle: DockHandle) -> Option<&mut Dock> {
+        self.docks.iter_mut().find(|dock| dock.handle == handle)
+    }
+
+    /// Returns dock of the active tab
+    pub fn active_dock(&self) -> Option<&Dock> {
+        self.docks.get(self.active_dock)
+    }
+
+    /// Makes tab at `index` the active one. Other tabs are left untouched. Returns false if there
+    /// is no tab at `index`
+    pub fn set_active_tab(&mut self, index: usize) -> bool {
+        if index < self.docks.len() {
+            self.active_dock = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn pinned_count(&self) -> usize {
+        self.docks.iter().filter(|dock| dock.pinned).count()
+    }
+
+    /// Runs `f` and moves `active_dock` so the same dock stays active
+    fn keep_active<F: FnOnce(&mut Container)>(&mut self, f: F) {
+        let active = self.active_dock().map(|dock| dock.handle);
+        f(self);
+        self.active_dock = active
+            .and_then(|handle| self.docks.iter().position(|dock| dock.handle == handle))
+            .unwrap_or(self.active_dock.min(self.docks.len().saturating_sub(1)));
+    }
+
+    /// Moves tab from index `from` to `to`. Pinned tabs always stay in front of unpinned ones so
+    /// the target index is clamped to that. Returns index where the tab ended up or None if
+    /// `from` is out of range.
+    pub fn reorder_tab(&mut self, from: usize, to: usize) -> Option<usize> {
+        if from >= self.docks.len() {
+            return None;
+        }
+        let mut res = from;
+        self.keep_active(|c| {
+            let dock = c.docks.remove(from);
+            let pinned_count = c.pinned_count();
+            res = if dock.pinned {
+                to.min(pinned_count)
+            } else {
+                to.max(pinned_count).min(c.docks.len())
+            };
+            c.docks.insert(res, dock);
+        });
+        Some(res)
+    }
+
+    /// Pins or unpins tab at `index`. The tab is moved to the boundary between pinned and
+    /// unpinned tabs. Returns new index of the tab or None if `index` is out of range.
+    pub fn set_tab_pinned(&mut self, index: usize, pinned: bool) -> Option<usize> {
+        if index >= self.docks.len() {
+            return None;
+        }
+        let mut res = index;
+        self.keep_active(|c| {
+            let mut dock = c.docks.remove(index);
+            dock.pinned = pinned;
+            res = c.pinned_count();
+            c.docks.insert(res, dock);
+        });
+        Some(res)
+    }
+
+    /// Removes tab at `index`. Pinned tabs are only removed when `force` is set.
+    pub fn remove_tab(&mut self, index: usize, force: bool) -> Option<Dock> {
+        if index >= self.docks.len() || (self.docks[index].pinned && !force) {
+            return None;
+        }
+        let mut res = None;
+        self.keep_active(|c| res = Some(c.docks.remove(index)));
+        res
+    }
+
+    /// Creates split with one container per tab, all of the same size. Tab order is kept. Docks
+    /// without a tab group get the handle of the first dock as the group id so they can be put
+    /// back together with `Area::regroup`.
+    pub fn explode_to_split(&self, direction: Direction, handle: SplitHandle) -> Split {
+        let group_id = self.docks.first().map(|dock| dock.handle.0);
+        let children = self.docks.iter()
+            .map(|dock| {
+                let mut dock = dock.clone();
+                dock.tab_group_id = dock.tab_group_id.or(group_id);
+                Area::Container(Container::new(dock, Rect::default()))
+            })
+            .collect();
+        Split::from_children(direction, handle, self.rect, children)
+    }
+
     pub fn get_dock_handle_at_pos(&self, pos: (f32, f32)) -> Option<DockHandle> {
         if self.rect.point_is_inside(pos) {
-            self.docks.first().map(|dock| dock.handle)
+            self.active_dock().map(|dock| dock.handle)
         } else {
             None
         }
     }
 
     pub fn get_header_rect(&self) -> Rect {
-        Rect::new(self.rect.x, self.rect.y, self.rect.width - 30.0, 30.0)
+        let y = match self.tab_position {
+            TabPosition::Top => self.rect.y,
+            TabPosition::Bottom => self.rect.y + self.rect.height - HEADER_HEIGHT,
+        };
+        Rect::new(self.rect.x, y, self.rect.width - 30.0, HEADER_HEIGHT)
+    }
+
+    /// Returns rect of the container without the tab strip
+    pub fn content_rect(&self) -> Rect {
+        let height = (self.rect.height - HEADER_HEIGHT).max(0.0);
+        let y = match self.tab_position {
+            TabPosition::Top => self.rect.y + HEADER_HEIGHT,
+            TabPosition::Bottom => self.rect.y,
+        };
+        Rect::new(self.rect.x, y, self.rect.width, height)
     }
 
     pub fn get_drag_target_at_pos(&self, pos: (f32, f32)) -> Option<DragTarget> {
         return if self.get_header_rect().point_is_inside(pos) {
-            Some(DragTarget::Dock(self.docks.first().unwrap().handle))
+            self.active_dock().map(|dock| DragTarget::Dock(dock.handle))
         } else {
             None
         }
@@ -47,7 +192,7 @@ impl Container {
 
     pub fn get_drop_target_at_pos(&self, pos: (f32, f32)) -> Option<DropTarget> {
         return if self.get_header_rect().point_is_inside(pos) {
-            Some(DropTarget::Dock(self.docks.first().unwrap().handle))
+            self.active_dock().map(|dock| DropTarget::Dock(dock.handle))
         } else {
             None
         }
@@ -57,19 +202,34 @@ impl Container {
 #[cfg(test)]
 mod test {
     extern crate serde_json;
-    use {Container, Dock, DockHandle, Rect};
+    use {Container, Dock, DockHandle, Rect, SplitStyle, Direction, SplitHandle, Area, TabPosition};
 
     #[test]
     fn test_container_serialize_0() {
         let container_in = Container {
             docks: Vec::new(),
-            rect: Rect::new(4.0, 5.0, 2.0, 8.0)
+            rect: Rect::new(4.0, 5.0, 2.0, 8.0),
+            active_dock: 0,
+            accepts_drops: false,
+            min_size: (20.0, 30.0),
+            preferred_size: Some((120.0, 80.0)),
+            style: Some(SplitStyle::new(0x11223344, 1.0)),
+            fixed: true,
+            tab_position: TabPosition::Bottom,
+            elevated: true,
         };
 
         let serialized = serde_json::to_string(&container_in).unwrap();
         let container_out: Container = serde_json::from_str(&serialized).unwrap();
 
         assert_eq!(container_out.docks.len(), 0);
+        assert!(!container_out.accepts_drops);
+        assert_eq!(container_out.min_size, (20.0, 30.0));
+        assert_eq!(container_out.preferred_size, Some((120.0, 80.0)));
+        assert_eq!(container_out.style, Some(SplitStyle::new(0x11223344, 1.0)));
+        assert!(container_out.fixed);
+        assert_eq!(container_out.tab_position, TabPosition::Bottom);
+        assert!(!container_out.elevated);
         // expect that rect is not serialized and set to zero
         assert_eq!(container_out.rect.x as i32, 0);
         assert_eq!(container_out.rect.y as i32, 0);
@@ -84,9 +244,20 @@ mod test {
                 handle: DockHandle(1),
                 plugin_name: "registers".to_owned(),
                 plugin_data: Some(vec!["some_data".to_owned(), "more_data".to_owned()]),
-                rect: Rect::new(4.0, 5.0, 2.0, 8.0)
+                rect: Rect::new(4.0, 5.0, 2.0, 8.0),
+                pinned: false,
+                tab_group_id: None,
+                content_scale: 1.0,
             }],
             rect: Rect::default(),
+            active_dock: 0,
+            accepts_drops: true,
+            min_size: (0.0, 0.0),
+            preferred_size: None,
+            style: None,
+            fixed: false,
+            tab_position: TabPosition::Top,
+            elevated: false,
         };
 
         let serialized = serde_json::to_string(&container_in).unwrap();
@@ -94,5 +265,99 @@ mod test {
 
         assert_eq!(container_out.docks.len(), 1);
         assert_eq!(container_out.docks[0].plugin_name, "registers");
+        assert_eq!(container_out.tab_position, TabPosition::Top);
+    }
+
+    #[test]
+    fn test_bottom_tabs() {
+        let mut container = Container::new(Dock::new(DockHandle(1), "a"), Rect::new(10.0, 20.0, 200.0, 100.0));
+        assert_eq!(container.content_rect(), Rect::new(10.0, 50.0, 200.0, 70.0));
+
+        container.tab_position = TabPosition::Bottom;
+        let header = container.get_header_rect();
+        assert_eq!(header.y, 90.0);
+        assert_eq!(header.y + header.height, 120.0);
+        assert_eq!(container.content_rect(), Rect::new(10.0, 20.0, 200.0, 70.0));
+
+        assert_eq!(container.get_drag_target_at_pos((20.0, 100.0)), Some(::DragTarget::Dock(DockHandle(1))));
+        assert_eq!(container.get_drag_target_at_pos((20.0, 30.0)), None);
+    }
+
+    #[test]
+    fn test_tab_data_kept_on_activation() {
+        let mut container = Container::new(Dock::new(DockHandle(1), "source"), Rect::default());
+        container.docks.push(Dock::new(DockHandle(2), "memory"));
+        container.find_dock_mut(DockHandle(1)).unwrap().plugin_data = Some(vec!["scroll=10".to_owned()]);
+        container.find_dock_mut(DockHandle(2)).unwrap().plugin_data = Some(vec!["scroll=99".to_owned()]);
+
+        assert!(container.set_active_tab(1));
+        assert_eq!(container.active_dock().unwrap().handle, DockHandle(2));
+        assert!(container.set_active_tab(0));
+        assert!(!container.set_active_tab(2));
+        assert_eq!(container.active_dock().unwrap().handle, DockHandle(1));
+
+        assert_eq!(container.docks[0].plugin_data, Some(vec!["scroll=10".to_owned()]));
+        assert_eq!(container.docks[1].plugin_data, Some(vec!["scroll=99".to_owned()]));
+    }
+
+    #[test]
+    fn test_pinned_tab() {
+        let mut container = Container::new(Dock::new(DockHandle(1), "a"), Rect::default());
+        container.docks.push(Dock::new(DockHandle(2), "b"));
+        container.docks.push(Dock::new(DockHandle(3), "overview"));
+        container.set_active_tab(1);
+
+        assert_eq!(container.set_tab_pinned(2, true), Some(0));
+        assert_eq!(container.set_tab_pinned(3, true), None);
+        assert_eq!(container.docks[0].handle, DockHandle(3));
+        assert_eq!(container.active_dock().unwrap().handle, DockHandle(2));
+
+        // unpinned tab can't be moved in front of the pinned one
+        assert_eq!(container.reorder_tab(2, 0), Some(1));
+        assert_eq!(container.reorder_tab(5, 0), None);
+        let order: Vec<DockHandle> = container.docks.iter().map(|dock| dock.handle).collect();
+        assert_eq!(order, vec![DockHandle(3), DockHandle(2), DockHandle(1)]);
+
+        assert!(container.remove_tab(0, false).is_none());
+        assert_eq!(container.docks.len(), 3);
+        assert_eq!(container.remove_tab(0, true).unwrap().handle, DockHandle(3));
+        assert_eq!(container.active_dock().unwrap().handle, DockHandle(2));
+    }
+
+    #[test]
+    fn test_targets_in_empty_container() {
+        let mut container = Container::new(Dock::new(DockHandle(1), "a"), Rect::new(0.0, 0.0, 300.0, 100.0));
+        let pos = (10.0, container.get_header_rect().y + 1.0);
+        assert!(container.get_drag_target_at_pos(pos).is_some());
+
+        container.remove_tab(0, true);
+        assert!(container.docks.is_empty());
+        assert!(container.get_drag_target_at_pos(pos).is_none());
+        assert!(container.get_drop_target_at_pos(pos).is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_explode_to_split() {
+        let mut container = Container::new(Dock::new(DockHandle(1), "a"), Rect::new(0.0, 0.0, 300.0, 100.0));
+        container.docks.push(Dock::new(DockHandle(2), "b"));
+        container.docks.push(Dock::new(DockHandle(3), "c"));
+
+        let split = container.explode_to_split(Direction::Vertical, SplitHandle(4));
+        assert_eq!(split.handle, SplitHandle(4));
+        assert_eq!(split.children.len(), 3);
+        assert_eq!(split.ratios.len(), 3);
+        assert!((split.ratios[0] - 1.0 / 3.0).abs() < 0.0001);
+        assert!((split.ratios[1] - 2.0 / 3.0).abs() < 0.0001);
+        assert_eq!(split.ratios[2], 1.0);
+        for (i, child) in split.children.iter().enumerate() {
+            match *child {
+                Area::Container(ref c) => {
+                    assert_eq!(c.docks.len(), 1);
+                    assert_eq!(c.docks[0].handle, DockHandle(i as u64 + 1));
+                    assert!((c.rect.width - 100.0).abs() < 0.001);
+                },
+                _ => panic!("Expected container"),
+            }
+        }
+    }
+}
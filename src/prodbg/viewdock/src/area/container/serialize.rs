@@ -1,5 +1,5 @@
 extern crate serde;
-use super::Container;
+use super::{Container, TabPosition};
 use rect::Rect;
 
 // Serialization
@@ -16,7 +16,14 @@ struct ContainerMapVisitor<'a> {
 
 impl<'a> serde::ser::MapVisitor for ContainerMapVisitor<'a> {
     fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
-        try!(serializer.serialize_struct_elt("docks", &self.value.docks));
+        serializer.serialize_struct_elt("docks", &self.value.docks)?;
+        serializer.serialize_struct_elt("active_dock", self.value.active_dock)?;
+        serializer.serialize_struct_elt("accepts_drops", self.value.accepts_drops)?;
+        serializer.serialize_struct_elt("min_size", self.value.min_size)?;
+        serializer.serialize_struct_elt("preferred_size", self.value.preferred_size)?;
+        serializer.serialize_struct_elt("fixed", self.value.fixed)?;
+        serializer.serialize_struct_elt("style", self.value.style)?;
+        serializer.serialize_struct_elt("tab_position", self.value.tab_position)?;
         Ok(None)
     }
 }
@@ -25,7 +32,7 @@ impl<'a> serde::ser::MapVisitor for ContainerMapVisitor<'a> {
 
 impl serde::Deserialize for Container {
     fn deserialize<D>(deserializer: &mut D) -> Result<Container, D::Error> where D: serde::de::Deserializer {
-        static FIELDS: &'static [&'static str] = &["docks"];
+        static FIELDS: &[&str] = &["docks", "active_dock", "accepts_drops", "min_size", "preferred_size", "style", "fixed", "tab_position"];
         deserializer.deserialize_struct("Container", FIELDS, ContainerVisitor)
     }
 }
@@ -37,10 +44,24 @@ impl serde::de::Visitor for ContainerVisitor {
 
     fn visit_map<V>(&mut self, mut visitor: V) -> Result<Container, V::Error> where V: serde::de::MapVisitor {
         let mut docks = None;
+        let mut active_dock = None;
+        let mut accepts_drops = None;
+        let mut min_size = None;
+        let mut preferred_size = None;
+        let mut style = None;
+        let mut fixed = None;
+        let mut tab_position = None;
 
         loop {
-            match try!(visitor.visit_key()) {
-                Some(ContainerField::Docks) => { docks = Some(try!(visitor.visit_value())); }
+            match visitor.visit_key()? {
+                Some(ContainerField::Docks) => { docks = Some(visitor.visit_value()?); }
+                Some(ContainerField::ActiveDock) => { active_dock = Some(visitor.visit_value()?); }
+                Some(ContainerField::AcceptsDrops) => { accepts_drops = Some(visitor.visit_value()?); }
+                Some(ContainerField::MinSize) => { min_size = Some(visitor.visit_value()?); }
+                Some(ContainerField::PreferredSize) => { preferred_size = visitor.visit_value()?; }
+                Some(ContainerField::Style) => { style = visitor.visit_value()?; }
+                Some(ContainerField::Fixed) => { fixed = Some(visitor.visit_value()?); }
+                Some(ContainerField::TabPosition) => { tab_position = Some(visitor.visit_value()?); }
                 None => { break; }
             }
         }
@@ -50,17 +71,39 @@ impl serde::de::Visitor for ContainerVisitor {
             None => Vec::new(),
         };
 
-        try!(visitor.end());
+        let active_dock = match active_dock {
+            Some(index) if index < docks.len() => index,
+            _ => 0,
+        };
+        let accepts_drops = accepts_drops.unwrap_or(true);
+        let min_size = min_size.unwrap_or((0.0, 0.0));
+
+        visitor.end()?;
 
         Ok(Container {
-            docks: docks,
+            docks,
             rect: Rect::default(), // We use default here as this is always recalculated
+            active_dock,
+            accepts_drops,
+            min_size,
+            preferred_size,
+            style,
+            fixed: fixed.unwrap_or(false),
+            tab_position: tab_position.unwrap_or(TabPosition::Top),
+            elevated: false,
         })
     }
 }
 
 enum ContainerField {
     Docks,
+    ActiveDock,
+    AcceptsDrops,
+    MinSize,
+    PreferredSize,
+    Style,
+    Fixed,
+    TabPosition,
 }
 
 impl serde::Deserialize for ContainerField  {
@@ -74,7 +117,14 @@ impl serde::Deserialize for ContainerField  {
                 where E: serde::de::Error {
                     match value {
                         "docks" => Ok(ContainerField::Docks),
-                        _ => Err(serde::de::Error::custom("expected docks")),
+                        "active_dock" => Ok(ContainerField::ActiveDock),
+                        "accepts_drops" => Ok(ContainerField::AcceptsDrops),
+                        "min_size" => Ok(ContainerField::MinSize),
+                        "preferred_size" => Ok(ContainerField::PreferredSize),
+                        "style" => Ok(ContainerField::Style),
+                        "fixed" => Ok(ContainerField::Fixed),
+                        "tab_position" => Ok(ContainerField::TabPosition),
+                        _ => Err(serde::de::Error::custom("expected docks, active_dock, accepts_drops, min_size, preferred_size, style, fixed or tab_position")),
                     }
                 }
         }
@@ -82,3 +132,77 @@ impl serde::Deserialize for ContainerField  {
         deserializer.deserialize(ContainerFieldVisitor)
     }
 }
+
+// Serialization of TabPosition
+
+impl serde::ser::Serialize for TabPosition {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: serde::ser::Serializer {
+        match *self {
+            TabPosition::Top => serde::ser::Serializer::serialize_unit_variant(serializer, "TabPosition", 0usize, "Top"),
+            TabPosition::Bottom => serde::ser::Serializer::serialize_unit_variant(serializer, "TabPosition", 1usize, "Bottom"),
+        }
+    }
+}
+
+// Deserialization of TabPosition
+
+impl serde::Deserialize for TabPosition {
+    fn deserialize<D>(deserializer: &mut D) -> Result<TabPosition, D::Error> where D: serde::de::Deserializer {
+        const VARIANTS: &[&str] = &["Top", "Bottom"];
+        deserializer.deserialize_enum("TabPosition", VARIANTS, TabPositionVisitor)
+    }
+}
+
+struct TabPositionVisitor;
+
+impl serde::de::EnumVisitor for TabPositionVisitor {
+    type Value = TabPosition;
+
+    fn visit<V>(&mut self, mut visitor: V) -> Result<TabPosition, V::Error> where V: serde::de::VariantVisitor {
+        match visitor.visit_variant()? {
+            TabPositionField::Top => {
+                visitor.visit_unit()?;
+                Ok(TabPosition::Top)
+            },
+            TabPositionField::Bottom => {
+                visitor.visit_unit()?;
+                Ok(TabPosition::Bottom)
+            }
+        }
+    }
+}
+
+enum TabPositionField {
+    Top,
+    Bottom,
+}
+
+impl serde::Deserialize for TabPositionField  {
+    fn deserialize<D>(deserializer: &mut D) -> Result<TabPositionField, D::Error> where D: serde::de::Deserializer {
+        struct TabPositionFieldVisitor;
+
+        impl serde::de::Visitor for TabPositionFieldVisitor {
+            type Value = TabPositionField;
+
+            fn visit_usize<E>(&mut self, value: usize) -> Result<TabPositionField, E>
+                where E: serde::de::Error {
+                    match value {
+                        0usize => Ok(TabPositionField::Top),
+                        1usize => Ok(TabPositionField::Bottom),
+                        _ => Err(serde::de::Error::invalid_value("expected a variant")),
+                    }
+                }
+
+            fn visit_str<E>(&mut self, value: &str) -> Result<TabPositionField, E>
+                where E: serde::de::Error {
+                    match value {
+                        "Top" => Ok(TabPositionField::Top),
+                        "Bottom" => Ok(TabPositionField::Bottom),
+                        _ => Err(serde::de::Error::invalid_value("expected a variant")),
+                    }
+                }
+        }
+
+        deserializer.deserialize_struct_field(TabPositionFieldVisitor)
+    }
+}
@@ -1,11 +1,43 @@
 mod container;
 mod split;
+mod style;
+mod ratio_tree;
 mod serialize;
 
-use rect::{Rect, Direction};
-use dock::DockHandle;
-pub use self::container::Container;
-pub use self::split::{SplitHandle, Split};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use serde_json;
+use rect::{Rect, Direction, Edge, EdgeSet};
+use dock::{Dock, DockHandle};
+use ResultView;
+pub use self::container::{Container, TabPosition, HEADER_HEIGHT};
+pub use self::split::{SplitHandle, Split, SIZER_WIDTH, MIN_RATIO, MAX_RATIO};
+pub use self::style::SplitStyle;
+pub use self::ratio_tree::{RatioNode, RatioChild};
+
+/// Owned copy of the leaves of a layout. Doesn't borrow the tree so it can be handed to e.g. a
+/// render thread while the layout keeps changing.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LayoutSnapshot {
+    /// Every dock with rect of its container
+    pub leaves: Vec<(DockHandle, Rect)>,
+    /// Hash of the tree structure (splits, directions and docks) without sizes. Equal hashes
+    /// mean only sizes have changed between snapshots.
+    pub structure_hash: u64,
+}
+
+/// Number of nodes of each type in a tree
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct NodeCounts {
+    pub splits: usize,
+    pub containers: usize,
+    pub docks: usize,
+    /// Floating windows. Only counted on workspace level
+    pub floating: usize,
+}
 
 /// Area could be occupied either by Container or by Split
 #[derive(Debug, Clone)]
@@ -30,6 +62,26 @@ impl Area {
         }
     }
 
+    /// Borrows split with supplied SplitHandle
+    pub fn split(&self, handle: SplitHandle) -> Option<&Split> {
+        match *self {
+            Area::Container(_) => None,
+            Area::Split(ref s) => if s.handle == handle {
+                Some(s)
+            } else {
+                s.children.iter()
+                    .filter_map(|child| child.split(handle))
+                    .next()
+            }
+        }
+    }
+
+    /// Serializes the split with `handle` and everything below it, e.g. for copying a group of
+    /// panels to another workspace with `Workspace::paste_subtree`
+    pub fn serialize_subtree(&self, handle: SplitHandle) -> Option<String> {
+        self.split(handle).map(|s| serde_json::to_string(&Area::Split(s.clone())).unwrap())
+    }
+
     pub fn update_rect(&mut self, rect: Rect) {
         match self {
             &mut Area::Container(ref mut c) => c.rect = rect,
@@ -44,6 +96,44 @@ impl Area {
         }
     }
 
+    /// Minimum size of the area along the axis that splits with `direction` divide
+    pub fn min_extent(&self, direction: Direction) -> f32 {
+        match *self {
+            Area::Container(ref c) => match direction {
+                Direction::Vertical => c.min_size.0,
+                Direction::Horizontal => c.min_size.1,
+            },
+            Area::Split(ref s) => {
+                let extents = s.children.iter().map(|child| child.min_extent(direction));
+                if s.direction == direction {
+                    extents.fold(0.0, |sum, extent| sum + extent)
+                } else {
+                    extents.fold(0.0, f32::max)
+                }
+            }
+        }
+    }
+
+    /// Preferred size of the area along the axis that splits with `direction` divide. Only
+    /// containers have one.
+    pub fn preferred_extent(&self, direction: Direction) -> Option<f32> {
+        match *self {
+            Area::Container(ref c) => c.preferred_size.map(|size| match direction {
+                Direction::Vertical => size.0,
+                Direction::Horizontal => size.1,
+            }),
+            Area::Split(_) => None,
+        }
+    }
+
+    /// Returns true if the area should keep its size when the parent split is resized
+    pub fn is_fixed(&self) -> bool {
+        match *self {
+            Area::Container(ref c) => c.fixed,
+            Area::Split(_) => false,
+        }
+    }
+
     /// Finds Container with supplied DockHandle
     pub fn find_container_by_dock_handle(&self, handle: DockHandle) -> Option<&Container> {
         match self {
@@ -55,6 +145,228 @@ impl Area {
         }
     }
 
+    /// Borrows dock with supplied DockHandle
+    pub fn dock(&self, handle: DockHandle) -> Option<&Dock> {
+        self.find_container_by_dock_handle(handle)
+            .and_then(|container| container.find_dock(handle))
+    }
+
+    /// Mutably borrows dock with supplied DockHandle
+    pub fn dock_mut(&mut self, handle: DockHandle) -> Option<&mut Dock> {
+        match *self {
+            Area::Container(ref mut c) => c.find_dock_mut(handle),
+            Area::Split(ref mut s) => s.children.iter_mut()
+                .filter_map(|child| child.dock_mut(handle))
+                .next(),
+        }
+    }
+
+    /// Puts `new` in place of dock with handle `old` keeping its position among the tabs and
+    /// its pinned state. Returns the old dock or None (and drops `new`) if `old` isn't found.
+    pub fn replace_dock(&mut self, old: DockHandle, mut new: Dock) -> Option<Dock> {
+        self.dock_mut(old).map(|slot| {
+            new.rect = slot.rect;
+            new.pinned = slot.pinned;
+            mem::replace(slot, new)
+        })
+    }
+
+    /// Replaces container holding the dock with a new split that has the container as its only
+    /// child. The split takes the rect of the container so nothing moves until a sibling is
+    /// added. Returns false if the dock isn't found.
+    pub fn wrap_child_in_split(&mut self, handle: DockHandle, direction: Direction, split_handle: SplitHandle) -> bool {
+        let is_target = match *self {
+            Area::Container(ref c) => c.find_dock(handle).is_some(),
+            Area::Split(ref mut s) => return s.children.iter_mut()
+                .any(|child| child.wrap_child_in_split(handle, direction, split_handle)),
+        };
+        if is_target {
+            let rect = self.get_rect();
+            let child = self.clone();
+            *self = Area::Split(Split::from_children(direction, split_handle, rect, vec![child]));
+        }
+        is_target
+    }
+
+    /// Wraps split `handle` in a new split `new_handle` and puts `dock` at `edge` of it so the dock
+    /// spans all children of the wrapped split, e.g. a header over a row of views. The dock gets
+    /// `ratio` of the space. Returns false if the split isn't found.
+    pub fn add_spanning(&mut self, handle: SplitHandle, dock: Dock, edge: Edge, ratio: f32, new_handle: SplitHandle) -> bool {
+        let is_target = match *self {
+            Area::Container(_) => return false,
+            Area::Split(ref mut s) => if s.handle != handle {
+                return s.children.iter_mut()
+                    .any(|child| child.add_spanning(handle, dock.clone(), edge, ratio, new_handle));
+            } else {
+                true
+            },
+        };
+        if is_target {
+            let rect = self.get_rect();
+            let old = self.clone();
+            let new = Area::Container(Container::new(dock, Rect::default()));
+            *self = Area::Split(match edge {
+                Edge::Left => Split::from_two(Direction::Vertical, ratio, new_handle, rect, new, old),
+                Edge::Top => Split::from_two(Direction::Horizontal, ratio, new_handle, rect, new, old),
+                Edge::Right => Split::from_two(Direction::Vertical, 1.0 - ratio, new_handle, rect, old, new),
+                Edge::Bottom => Split::from_two(Direction::Horizontal, 1.0 - ratio, new_handle, rect, old, new),
+            });
+        }
+        is_target
+    }
+
+    /// Moves all docks with `group_id` as tab group into the container of the first of them and
+    /// simplifies the tree to get rid of the emptied containers. Returns false if there is
+    /// nothing to group.
+    pub fn regroup(&mut self, group_id: u64) -> bool {
+        let handles: Vec<DockHandle> = self.docks_mut().iter()
+            .filter(|dock| dock.tab_group_id == Some(group_id))
+            .map(|dock| dock.handle)
+            .collect();
+        if handles.len() < 2 {
+            return false;
+        }
+        let docks: Vec<Dock> = handles[1..].iter()
+            .filter_map(|&handle| self.take_dock(handle))
+            .collect();
+        if let Some(container) = self.find_container_mut(handles[0]) {
+            container.docks.extend(docks);
+        }
+        self.simplify();
+        true
+    }
+
+    /// Replaces split `handle` with one container that has the docks of the split as tabs, first
+    /// one active. Nested splits are flattened, docks keep their order in the tree (depth first)
+    /// except that pinned docks are moved in front. Returns false if the split isn't found.
+    pub fn collapse_split_to_tabs(&mut self, handle: SplitHandle) -> bool {
+        let is_target = match *self {
+            Area::Container(_) => return false,
+            Area::Split(ref mut s) => if s.handle != handle {
+                return s.children.iter_mut().any(|child| child.collapse_split_to_tabs(handle));
+            } else {
+                true
+            },
+        };
+        let mut docks: Vec<Dock> = self.docks_mut().into_iter().map(|dock| dock.clone()).collect();
+        if !is_target || docks.is_empty() {
+            return false;
+        }
+        docks.sort_by_key(|dock| !dock.pinned);
+        let rect = self.get_rect();
+        let mut container = Container::new(docks.remove(0), rect);
+        container.docks.extend(docks);
+        *self = Area::Container(container);
+        self.update_rect(rect);
+        true
+    }
+
+    fn take_dock(&mut self, handle: DockHandle) -> Option<Dock> {
+        match *self {
+            Area::Container(ref mut c) => c.docks.iter()
+                .position(|dock| dock.handle == handle)
+                .and_then(|index| c.remove_tab(index, true)),
+            Area::Split(ref mut s) => s.children.iter_mut()
+                .filter_map(|child| child.take_dock(handle))
+                .next(),
+        }
+    }
+
+    /// First container of the area along its splits, or the last one if `first` is false
+    fn edge_container_mut(&mut self, first: bool) -> Option<&mut Container> {
+        match *self {
+            Area::Container(ref mut c) => Some(c),
+            Area::Split(ref mut s) => {
+                let child = if first { s.children.first_mut() } else { s.children.last_mut() };
+                child.and_then(|child| child.edge_container_mut(first))
+            }
+        }
+    }
+
+    fn find_container_mut(&mut self, handle: DockHandle) -> Option<&mut Container> {
+        match *self {
+            Area::Container(ref mut c) => if c.find_dock(handle).is_some() { Some(c) } else { None },
+            Area::Split(ref mut s) => s.children.iter_mut()
+                .filter_map(|child| child.find_container_mut(handle))
+                .next(),
+        }
+    }
+
+    /// Returns handles of all docks (including inactive tabs) in the subtree of split `handle`.
+    /// Empty if the split isn't found.
+    pub fn docks_under_split(&self, handle: SplitHandle) -> Vec<DockHandle> {
+        self.split(handle).map_or(Vec::new(), |s| s.children.iter()
+            .flat_map(|child| child.leaves_with_depth().map(|(handle, _, _)| handle))
+            .collect())
+    }
+
+    /// Returns a copy of the area with only the docks in `keep`. Empty containers are dropped,
+    /// splits left with one child are replaced by it and other splits give their remaining
+    /// children equal space. Returns None if no dock is kept.
+    pub fn filtered(&self, keep: &[DockHandle]) -> Option<Area> {
+        match *self {
+            Area::Container(ref c) => {
+                let active = c.docks.get(c.active_dock).map(|dock| dock.handle);
+                let mut res = c.clone();
+                res.docks.retain(|dock| keep.contains(&dock.handle));
+                if res.docks.is_empty() {
+                    return None;
+                }
+                res.active_dock = res.docks.iter().position(|dock| Some(dock.handle) == active).unwrap_or(0);
+                Some(Area::Container(res))
+            },
+            Area::Split(ref s) => {
+                let mut children: Vec<Area> = s.children.iter()
+                    .filter_map(|child| child.filtered(keep))
+                    .collect();
+                match children.len() {
+                    0 => None,
+                    1 => children.pop(),
+                    _ => {
+                        let mut res = Split::from_children(s.direction, s.handle, s.rect, children);
+                        res.style = s.style;
+                        res.hysteresis = s.hysteresis;
+                        Some(Area::Split(res))
+                    },
+                }
+            },
+        }
+    }
+
+    /// Mutably borrows all docks in the tree
+    pub fn docks_mut(&mut self) -> Vec<&mut Dock> {
+        match *self {
+            Area::Container(ref mut c) => c.docks.iter_mut().collect(),
+            Area::Split(ref mut s) => s.children.iter_mut()
+                .flat_map(|child| child.docks_mut())
+                .collect(),
+        }
+    }
+
+    /// Returns docks that are currently shown, one per container, in the order they should be
+    /// drawn. Docks of elevated containers come last.
+    pub fn visible_docks(&self) -> Vec<&Dock> {
+        let mut containers = Vec::new();
+        self.collect_containers(&mut containers);
+        containers.sort_by_key(|c| c.elevated);
+        containers.iter()
+            .filter_map(|c| c.active_dock())
+            .collect()
+    }
+
+    /// Returns the shown dock that has the largest container
+    pub fn largest_dock(&self) -> Option<DockHandle> {
+        let mut containers = Vec::new();
+        self.collect_containers(&mut containers);
+        containers.iter()
+            .filter_map(|c| c.active_dock().map(|dock| (dock.handle, c.rect.width * c.rect.height)))
+            .fold(None, |best: Option<(DockHandle, f32)>, (handle, size)| match best {
+                Some((_, best_size)) if best_size >= size => best,
+                _ => Some((handle, size)),
+            })
+            .map(|(handle, _)| handle)
+    }
+
     /// Finds Area::Split which contains Container with supplied DockHandle
     pub fn find_split_by_dock_handle(&mut self, handle: DockHandle) -> Option<(&mut Split, usize)> {
         if let &mut Area::Split(ref mut s) = self {
@@ -94,10 +406,12 @@ impl Area {
         return None;
     }
 
-    pub fn get_drag_target_at_pos(&self, pos: (f32, f32)) -> Option<DragTarget> {
-        match self {
-            &Area::Split(ref s) => s.get_drag_target_at_pos(pos),
-            &Area::Container(ref c) => c.get_drag_target_at_pos(pos),
+    /// Finds what can be dragged at `pos`. `sizer_width` is the width (in pixels) of the band
+    /// around split borders that grabs the sizer.
+    pub fn get_drag_target_at_pos(&self, pos: (f32, f32), sizer_width: f32) -> Option<DragTarget> {
+        match *self {
+            Area::Split(ref s) => s.get_drag_target_at_pos(pos, sizer_width),
+            Area::Container(ref c) => c.get_drag_target_at_pos(pos),
         }
     }
 
@@ -114,30 +428,641 @@ impl Area {
             &Area::Split(ref c) => c.get_dock_handle_at_pos(pos),
         };
     }
+
+    /// Checks child count limits of all splits in the tree. See `Split::validate`
+    pub fn validate(&self) -> ResultView<()> {
+        match *self {
+            Area::Container(_) => Ok(()),
+            Area::Split(ref s) => s.validate(),
+        }
+    }
+
+    /// Counts splits, containers and docks in the tree
+    pub fn node_counts(&self) -> NodeCounts {
+        let mut counts = NodeCounts::default();
+        self.add_node_counts(&mut counts);
+        counts
+    }
+
+    fn add_node_counts(&self, counts: &mut NodeCounts) {
+        match *self {
+            Area::Container(ref c) => {
+                counts.containers += 1;
+                counts.docks += c.docks.len();
+            },
+            Area::Split(ref s) => {
+                counts.splits += 1;
+                for child in &s.children {
+                    child.add_node_counts(counts);
+                }
+            }
+        }
+    }
+
+    /// Iterates over all docks with rect of their container and how many splits are above them.
+    /// Container at the root has depth 0.
+    pub fn leaves_with_depth(&self) -> impl Iterator<Item = (DockHandle, Rect, usize)> {
+        let mut leaves = Vec::new();
+        self.collect_leaves_with_depth(0, &mut leaves);
+        leaves.into_iter()
+    }
+
+    fn collect_leaves_with_depth(&self, depth: usize, target: &mut Vec<(DockHandle, Rect, usize)>) {
+        match *self {
+            Area::Container(ref c) => for dock in &c.docks {
+                target.push((dock.handle, c.rect, depth));
+            },
+            Area::Split(ref s) => for child in &s.children {
+                child.collect_leaves_with_depth(depth + 1, target);
+            },
+        }
+    }
+
+    /// Maps rect of every dock into an atlas of `atlas_size` keeping the relative placement, e.g.
+    /// for rendering all views into one texture. Tabs of a container share the same rect.
+    pub fn pack_atlas(&self, atlas_size: (f32, f32)) -> HashMap<DockHandle, Rect> {
+        let root = self.get_rect();
+        let scale_x = if root.width > 0.0 { atlas_size.0 / root.width } else { 0.0 };
+        let scale_y = if root.height > 0.0 { atlas_size.1 / root.height } else { 0.0 };
+        self.leaves_with_depth().map(|(handle, rect, _)| {
+            (handle, Rect::new((rect.x - root.x) * scale_x,
+                               (rect.y - root.y) * scale_y,
+                               rect.width * scale_x,
+                               rect.height * scale_y))
+        }).collect()
+    }
+
+    /// Returns how much of the area each dock covers. Only the active tab of a container gets its
+    /// space, other tabs are reported as 0 so the fractions sum to 1.
+    pub fn area_fractions(&self) -> HashMap<DockHandle, f32> {
+        let root = self.get_rect();
+        let total = root.width * root.height;
+        let mut containers = Vec::new();
+        self.collect_containers(&mut containers);
+        let mut fractions = HashMap::new();
+        for c in containers {
+            let active = c.active_dock().map(|dock| dock.handle);
+            for dock in &c.docks {
+                let fraction = if Some(dock.handle) == active && total > 0.0 {
+                    c.rect.width * c.rect.height / total
+                } else {
+                    0.0
+                };
+                fractions.insert(dock.handle, fraction);
+            }
+        }
+        fractions
+    }
+
+    /// Captures leaves and structure of the tree in an owned snapshot
+    pub fn snapshot(&self) -> LayoutSnapshot {
+        let mut hasher = DefaultHasher::new();
+        self.hash_structure(&mut hasher);
+        LayoutSnapshot {
+            leaves: self.leaves_with_depth().map(|(handle, rect, _)| (handle, rect)).collect(),
+            structure_hash: hasher.finish(),
+        }
+    }
+
+    fn hash_structure(&self, hasher: &mut DefaultHasher) {
+        match *self {
+            Area::Container(ref c) => {
+                0u8.hash(hasher);
+                c.docks.len().hash(hasher);
+                for dock in &c.docks {
+                    dock.handle.hash(hasher);
+                }
+            },
+            Area::Split(ref s) => {
+                1u8.hash(hasher);
+                s.handle.0.hash(hasher);
+                (s.direction == Direction::Vertical).hash(hasher);
+                s.children.len().hash(hasher);
+                for child in &s.children {
+                    child.hash_structure(hasher);
+                }
+            },
+        }
+    }
+
+    /// Returns true if both areas show the same docks at the same places, no matter how the
+    /// trees are structured.
+    pub fn visually_equivalent(&self, other: &Area, epsilon: f32) -> bool {
+        let leaves: Vec<(DockHandle, Rect, usize)> = self.leaves_with_depth().collect();
+        let other_leaves: Vec<(DockHandle, Rect, usize)> = other.leaves_with_depth().collect();
+        leaves.len() == other_leaves.len() && leaves.iter().all(|&(handle, rect, _)| {
+            other_leaves.iter().any(|&(other_handle, ref other_rect, _)| {
+                handle == other_handle && rect.approx_eq(other_rect, epsilon)
+            })
+        })
+    }
+
+    /// Flips the layout by reversing children of every split with the given direction. Use
+    /// `Direction::Vertical` to flip left and right and `Direction::Horizontal` to flip top and
+    /// bottom.
+    pub fn mirror(&mut self, direction: Direction) {
+        if let &mut Area::Split(ref mut s) = self {
+            for child in s.children.iter_mut() {
+                child.mirror(direction);
+            }
+            if s.direction == direction {
+                s.reverse_children();
+            }
+        }
+    }
+
+    /// Gives equal space to children of every split with the given direction. Splits with the
+    /// other direction are left untouched.
+    pub fn equalize_direction(&mut self, direction: Direction) {
+        if let &mut Area::Split(ref mut s) = self {
+            if s.direction == direction {
+                s.equalize();
+            }
+            for child in s.children.iter_mut() {
+                child.equalize_direction(direction);
+            }
+        }
+    }
+
+    /// Returns handles of splits from this area down to the container holding the dock
+    pub fn split_path_to_dock(&self, handle: DockHandle) -> Option<Vec<SplitHandle>> {
+        match *self {
+            Area::Container(ref c) => c.find_dock(handle).map(|_| Vec::new()),
+            Area::Split(ref s) => s.children.iter()
+                .filter_map(|child| child.split_path_to_dock(handle))
+                .next()
+                .map(|mut path| {
+                    path.insert(0, s.handle);
+                    path
+                }),
+        }
+    }
+
+    /// Finds the sizer next to the area holding the dock in a split with `direction`. Sizer after
+    /// the area is picked when `after` is true and the one before it otherwise. Lowest matching
+    /// split is used. Returns handle of the split and index of the sizer.
+    pub fn sizer_next_to_dock(&self, handle: DockHandle, direction: Direction, after: bool) -> Option<(SplitHandle, usize)> {
+        let s = match *self {
+            Area::Container(_) => return None,
+            Area::Split(ref s) => s,
+        };
+        let index = s.children.iter().position(|child| child.dock(handle).is_some())?;
+        if let Some(res) = s.children[index].sizer_next_to_dock(handle, direction, after) {
+            return Some(res);
+        }
+        if s.direction != direction {
+            return None;
+        }
+        if after && index + 1 < s.children.len() {
+            Some((s.handle, index))
+        } else if !after && index > 0 {
+            Some((s.handle, index - 1))
+        } else {
+            None
+        }
+    }
+
+    /// Finds the dock on the other side of `edge` of the container holding `handle`. Of the
+    /// containers touching that edge the one with the longest shared border wins. Returns the
+    /// active dock of that container.
+    pub fn neighbor_dock(&self, handle: DockHandle, edge: Edge) -> Option<DockHandle> {
+        let rect = match self.find_container_by_dock_handle(handle) {
+            Some(c) => c.rect,
+            None => return None,
+        };
+        let epsilon = 0.5;
+        let overlap = |start: f32, length: f32, other_start: f32, other_length: f32| {
+            (start + length).min(other_start + other_length) - start.max(other_start)
+        };
+        let mut containers = Vec::new();
+        self.collect_containers(&mut containers);
+        containers.iter()
+            .filter(|c| c.find_dock(handle).is_none())
+            .filter_map(|c| {
+                let other = c.rect;
+                let (touches, shared) = match edge {
+                    Edge::Left => ((other.x + other.width - rect.x).abs() < epsilon,
+                                   overlap(rect.y, rect.height, other.y, other.height)),
+                    Edge::Right => ((rect.x + rect.width - other.x).abs() < epsilon,
+                                    overlap(rect.y, rect.height, other.y, other.height)),
+                    Edge::Top => ((other.y + other.height - rect.y).abs() < epsilon,
+                                  overlap(rect.x, rect.width, other.x, other.width)),
+                    Edge::Bottom => ((rect.y + rect.height - other.y).abs() < epsilon,
+                                     overlap(rect.x, rect.width, other.x, other.width)),
+                };
+                if touches && shared > 0.0 { Some((shared, c)) } else { None }
+            })
+            .fold(None, |best: Option<(f32, &&Container)>, (shared, c)| match best {
+                Some((best_shared, _)) if best_shared >= shared => best,
+                _ => Some((shared, c)),
+            })
+            .and_then(|(_, c)| c.active_dock().map(|dock| dock.handle))
+    }
+
+    /// Returns edges of the container holding the dock that have a sizer on them, i.e. edges
+    /// shared with a neighbor. Edges on the outer border of the layout are never included.
+    pub fn resizable_edges(&self, handle: DockHandle) -> EdgeSet {
+        let mut edges = EdgeSet::default();
+        let sides = [(Edge::Left, Direction::Vertical, false), (Edge::Right, Direction::Vertical, true),
+                     (Edge::Top, Direction::Horizontal, false), (Edge::Bottom, Direction::Horizontal, true)];
+        for &(edge, direction, after) in &sides {
+            if self.sizer_next_to_dock(handle, direction, after).is_some() {
+                edges.insert(edge);
+            }
+        }
+        edges
+    }
+
+    /// Finds the lowest split that has both docks in its subtree
+    pub fn common_ancestor(&self, first: DockHandle, second: DockHandle) -> Option<SplitHandle> {
+        let first_path = self.split_path_to_dock(first)?;
+        let second_path = self.split_path_to_dock(second)?;
+        first_path.iter().zip(second_path.iter())
+            .take_while(|&(a, b)| a == b)
+            .last()
+            .map(|(&handle, _)| handle)
+    }
+
+    /// Number of draggable sizers in the tree. Locked sizers aren't counted.
+    pub fn sizer_count(&self) -> usize {
+        match *self {
+            Area::Container(_) => 0,
+            Area::Split(ref s) => {
+                let own = (0..s.ratios.len().saturating_sub(1)).filter(|&i| !s.is_sizer_locked(i)).count();
+                s.children.iter().fold(own, |sum, child| sum + child.sizer_count())
+            }
+        }
+    }
+
+    /// Returns all sizers in the tree. `sizer_width` is the width of the sizer rects
+    pub fn all_sizers(&self, sizer_width: f32) -> Vec<SizerInfo> {
+        match *self {
+            Area::Container(_) => Vec::new(),
+            Area::Split(ref s) => {
+                let mut sizers = s.sizers(sizer_width);
+                for child in &s.children {
+                    sizers.extend(child.all_sizers(sizer_width));
+                }
+                sizers
+            }
+        }
+    }
+
+    /// Returns sizers that overlap `area`, e.g. for redrawing only part of the workspace
+    pub fn sizers_in_rect(&self, area: &Rect, sizer_width: f32) -> Vec<SizerInfo> {
+        self.all_sizers(sizer_width).into_iter()
+            .filter(|sizer| sizer.rect.intersects(area))
+            .collect()
+    }
+
+    /// Finds the lowest split whose rect fully contains `area`
+    pub fn enclosing_split_of_rect(&self, area: &Rect) -> Option<SplitHandle> {
+        match *self {
+            Area::Container(_) => None,
+            Area::Split(ref s) => {
+                if !s.rect.contains_rect(area) {
+                    return None;
+                }
+                s.children.iter()
+                    .filter_map(|child| child.enclosing_split_of_rect(area))
+                    .next()
+                    .or(Some(s.handle))
+            }
+        }
+    }
+
+    /// Returns the tree in Graphviz DOT format. Splits are labeled with direction and ratios and
+    /// docks with plugin name and handle. Tabs of a container hang from a shared `tabs` node.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph layout {\n");
+        self.write_dot(None, &mut out);
+        out.push_str("}\n");
+        out
+    }
+
+    fn write_dot(&self, parent: Option<&str>, out: &mut String) {
+        let link = |node: &str, out: &mut String| if let Some(parent) = parent {
+            out.push_str(&format!("    {} -> {};\n", parent, node));
+        };
+        match *self {
+            Area::Split(ref s) => {
+                let node = format!("split_{}", s.handle.0);
+                let ratios: Vec<String> = s.ratios.iter().map(|ratio| format!("{}", ratio)).collect();
+                out.push_str(&format!("    {} [shape=box, label=\"{:?}\\n{}\"];\n", node, s.direction, ratios.join(", ")));
+                link(&node, out);
+                for child in &s.children {
+                    child.write_dot(Some(&node), out);
+                }
+            },
+            Area::Container(ref c) => {
+                let tabs = if c.docks.len() > 1 {
+                    let node = format!("tabs_{}", c.docks[0].handle.0);
+                    out.push_str(&format!("    {} [shape=point, label=\"tabs\"];\n", node));
+                    link(&node, out);
+                    Some(node)
+                } else {
+                    None
+                };
+                for dock in &c.docks {
+                    let node = format!("dock_{}", dock.handle.0);
+                    out.push_str(&format!("    {} [label=\"{}\\n#{}\"];\n", node, dock.plugin_name.replace("\"", "\\\""), dock.handle.0));
+                    match tabs {
+                        Some(ref tabs) => out.push_str(&format!("    {} -> {};\n", tabs, node)),
+                        None => link(&node, out),
+                    }
+                }
+            },
+        }
+    }
+
+    /// Converts the layout to a `RatioNode` tree. Ratio of each split child is the share of the
+    /// split it takes, not the position of its border like in `Split::ratios`.
+    pub fn to_ratio_tree(&self) -> RatioNode {
+        match *self {
+            Area::Container(ref c) => {
+                let mut docks: Vec<RatioNode> = c.docks.iter()
+                    .map(|dock| RatioNode::Dock { handle: dock.handle, name: dock.plugin_name.clone() })
+                    .collect();
+                if docks.len() == 1 {
+                    docks.remove(0)
+                } else {
+                    RatioNode::Tabs(docks)
+                }
+            },
+            Area::Split(ref s) => {
+                let mut previous = 0.0;
+                let children = s.children.iter().zip(s.ratios.iter()).map(|(child, &ratio)| {
+                    let share = ratio - previous;
+                    previous = ratio;
+                    RatioChild { ratio: share, node: child.to_ratio_tree() }
+                }).collect();
+                RatioNode::Split { direction: s.direction, children }
+            },
+        }
+    }
+
+    /// Returns a standalone SVG wireframe of the layout scaled to `size`. Containers are drawn as
+    /// rectangles labeled with plugin name and handle of the active dock and sizers as lines.
+    pub fn to_svg(&self, size: (f32, f32)) -> String {
+        let bounds = self.get_rect();
+        let scale_x = if bounds.width > 0.0 { size.0 / bounds.width } else { 1.0 };
+        let scale_y = if bounds.height > 0.0 { size.1 / bounds.height } else { 1.0 };
+        let map = |rect: &Rect| Rect::new((rect.x - bounds.x) * scale_x, (rect.y - bounds.y) * scale_y,
+                                          rect.width * scale_x, rect.height * scale_y);
+
+        let mut out = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+                              size.0, size.1);
+        let mut containers = Vec::new();
+        self.collect_containers(&mut containers);
+        for c in containers {
+            let rect = map(&c.rect);
+            out.push_str(&format!("  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"black\"/>\n",
+                                  rect.x, rect.y, rect.width, rect.height));
+            if let Some(dock) = c.active_dock() {
+                let name = dock.plugin_name.replace("&", "&amp;").replace("<", "&lt;").replace(">", "&gt;");
+                out.push_str(&format!("  <text x=\"{}\" y=\"{}\" font-size=\"12\">{} #{}</text>\n",
+                                      rect.x + 4.0, rect.y + 14.0, name, dock.handle.0));
+            }
+        }
+        for sizer in self.all_sizers(0.0) {
+            let rect = map(&sizer.rect);
+            out.push_str(&format!("  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"red\"/>\n",
+                                  rect.x, rect.y, rect.x + rect.width, rect.y + rect.height));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Flips split directions so cells get closer to `target` aspect ratio (width / height).
+    ///
+    /// This is a greedy heuristic that works from the root down: for each split the children's
+    /// aspect ratios are computed for both directions with the current ratios and the direction
+    /// where the sum of `|ln(aspect / target)|` is smaller is kept. Ratios are never changed so
+    /// the relative sizes of the children stay the same. Children are laid out before their
+    /// own splits are considered.
+    pub fn rebalance_to_aspect(&mut self, target: f32) {
+        if let &mut Area::Split(ref mut s) = self {
+            if target > 0.0 && s.rect.width > 0.0 && s.rect.height > 0.0 {
+                let mut previous = 0.0;
+                let spans: Vec<f32> = s.ratios.iter().map(|&ratio| {
+                    let span = ratio - previous;
+                    previous = ratio;
+                    span
+                }).collect();
+                let score = |direction: Direction| spans.iter().fold(0.0, |sum, &span| {
+                    let aspect = match direction {
+                        Direction::Vertical => s.rect.width * span / s.rect.height,
+                        Direction::Horizontal => s.rect.width / (s.rect.height * span),
+                    };
+                    sum + if aspect > 0.0 { (aspect / target).ln().abs() } else { 0.0 }
+                });
+                let other = match s.direction {
+                    Direction::Vertical => Direction::Horizontal,
+                    Direction::Horizontal => Direction::Vertical,
+                };
+                if score(other) < score(s.direction) {
+                    s.set_direction(other, false);
+                }
+            }
+            for child in s.children.iter_mut() {
+                child.rebalance_to_aspect(target);
+            }
+        }
+    }
+
+    /// Returns true if area doesn't contain any docks
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            Area::Container(ref c) => c.docks.is_empty(),
+            Area::Split(ref s) => s.children.iter().all(|child| child.is_empty()),
+        }
+    }
+
+    /// Reduces the tree to the minimal structure with the same look: containers without docks
+    /// are removed, splits with the same direction as their parent are flattened into it,
+    /// children with a span below `MIN_RATIO` are folded into the neighbor that gets their space
+    /// (their docks become tabs of it) and splits with a single child are replaced by that child.
+    /// Docks are never removed. Running it on already simplified tree does nothing. Returns true
+    /// if the tree was changed.
+    pub fn simplify(&mut self) -> bool {
+        let mut changed = false;
+        let replacement = match *self {
+            Area::Container(_) => None,
+            Area::Split(ref mut s) => {
+                for child in s.children.iter_mut() {
+                    changed |= child.simplify();
+                }
+                let mut index = 0;
+                while index < s.children.len() && s.children.len() > 1 {
+                    if s.children[index].is_empty() {
+                        s.remove_child(index);
+                        changed = true;
+                    } else {
+                        index += 1;
+                    }
+                }
+                let mut index = 0;
+                while index < s.children.len() {
+                    let same_direction = match s.children[index] {
+                        Area::Split(ref child) => child.direction == s.direction,
+                        _ => false,
+                    };
+                    if same_direction {
+                        s.absorb_child_split(index);
+                        changed = true;
+                    } else {
+                        index += 1;
+                    }
+                }
+                let mut index = 0;
+                while index < s.children.len() && s.children.len() > 1 {
+                    let previous = if index == 0 { 0.0 } else { s.ratios[index - 1] };
+                    if s.ratios[index] - previous >= MIN_RATIO {
+                        index += 1;
+                        continue;
+                    }
+                    let docks: Vec<Dock> = s.children[index].docks_mut().into_iter().map(|dock| dock.clone()).collect();
+                    // The next child takes the space, or the previous one if this was the last
+                    s.remove_child(index);
+                    let (neighbor, first) = if index < s.children.len() { (index, true) } else { (index - 1, false) };
+                    if let Some(c) = s.children[neighbor].edge_container_mut(first) {
+                        c.docks.extend(docks);
+                    }
+                    changed = true;
+                }
+                if s.children.len() == 1 {
+                    s.children.pop()
+                } else {
+                    None
+                }
+            }
+        };
+        if let Some(child) = replacement {
+            let rect = self.get_rect();
+            *self = child;
+            self.update_rect(rect);
+            changed = true;
+        }
+        changed
+    }
+
+    /// Same as `get_dock_handle_at_pos` but when no dock is hit (for example when there is a gap
+    /// during animations) returns the visible dock closest to the point
+    pub fn nearest_dock_at_pos(&self, pos: (f32, f32)) -> Option<DockHandle> {
+        self.get_dock_handle_at_pos(pos).or_else(|| {
+            let mut containers = Vec::new();
+            self.collect_containers(&mut containers);
+            containers.iter()
+                .filter(|c| c.active_dock().is_some())
+                .map(|c| (c.rect.distance_to_point(pos), c))
+                .fold(None, |nearest: Option<(f32, &Container)>, (distance, c)| match nearest {
+                    Some((nearest_distance, _)) if nearest_distance <= distance => nearest,
+                    _ => Some((distance, c)),
+                })
+                .and_then(|(_, c)| c.active_dock().map(|dock| dock.handle))
+        })
+    }
+
+    /// Finds where `dragging` dock can be dropped at `pos`. If the target under the pointer
+    /// doesn't take the dock (it doesn't accept drops or already holds the dock) the closest
+    /// container that does is returned instead. Rect is the rect of the target container.
+    pub fn nearest_drop_target(&self, pos: (f32, f32), dragging: DockHandle) -> Option<(DropTarget, Rect)> {
+        let mut containers = Vec::new();
+        self.collect_containers(&mut containers);
+        let legal: Vec<&Container> = containers.into_iter()
+            .filter(|c| c.accepts_drops && c.active_dock().is_some() && c.find_dock(dragging).is_none())
+            .collect();
+
+        if let Some(DropTarget::Dock(handle)) = self.get_drop_target_at_pos(pos) {
+            if let Some(c) = legal.iter().find(|c| c.find_dock(handle).is_some()) {
+                return Some((DropTarget::Dock(handle), c.rect));
+            }
+        }
+
+        legal.iter()
+            .map(|c| (c.rect.distance_to_point(pos), c))
+            .fold(None, |nearest: Option<(f32, &&Container)>, (distance, c)| match nearest {
+                Some((nearest_distance, _)) if nearest_distance <= distance => nearest,
+                _ => Some((distance, c)),
+            })
+            .map(|(_, c)| (DropTarget::Dock(c.active_dock().unwrap().handle), c.rect))
+    }
+
+    /// Returns handles of all docks sorted by the position of their container on screen (top to
+    /// bottom, then left to right). Tabs of the same container keep their order.
+    pub fn docks_in_visual_order(&self) -> Vec<DockHandle> {
+        let mut containers = Vec::new();
+        self.collect_containers(&mut containers);
+        containers.sort_by(|a, b| {
+            a.rect.y.partial_cmp(&b.rect.y)
+                .unwrap_or(Ordering::Equal)
+                .then(a.rect.x.partial_cmp(&b.rect.x).unwrap_or(Ordering::Equal))
+        });
+        containers.iter()
+            .flat_map(|c| c.docks.iter().map(|dock| dock.handle))
+            .collect()
+    }
+
+    fn collect_containers<'a>(&'a self, target: &mut Vec<&'a Container>) {
+        match *self {
+            Area::Container(ref c) => target.push(c),
+            Area::Split(ref s) => for child in &s.children {
+                child.collect_containers(target);
+            },
+        }
+    }
 }
 
 
-#[derive(Debug)]
+/// Sizer between two children of a split
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SizerInfo {
+    pub handle: SplitHandle,
+    /// Index of the ratio the sizer controls
+    pub index: usize,
+    pub direction: Direction,
+    /// Area of the sizer as used for hit testing
+    pub rect: Rect,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum DragTarget {
     SplitSizer(SplitHandle, usize, Direction),
-    Dock(DockHandle)
+    Dock(DockHandle),
+    /// Outer edge of the whole workspace. Only reported when root edge resizing is enabled
+    RootEdge(Edge),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum DropTarget {
     Dock(DockHandle)
 }
 
+/// Reason why a dock can't be dropped on a target
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DropRejection {
+    /// Dragged dock was dropped on itself or on the container it's already in
+    SelfDrop,
+    /// Target container doesn't accept drops
+    TargetLocked,
+    /// Dragged dock isn't in the layout
+    DockNotFound,
+    /// Target isn't in the layout
+    TargetNotFound,
+    /// Moving the dock away would leave its split with fewer children than `min_children`
+    BelowMinChildren,
+}
+
 
 #[cfg(test)]
 mod test {
     extern crate serde_json;
 
-    use {Area};
+    use {Area, NodeCounts, LayoutSnapshot, DropTarget, RatioNode, RatioChild};
     use super::container::Container;
     use super::split::{Split, SplitHandle};
     use dock::{Dock, DockHandle};
-    use rect::{Rect, Direction};
+    use rect::{Rect, Direction, Edge, EdgeSet};
 
     #[test]
     fn test_area_serialize() {
@@ -165,4 +1090,591 @@ mod test {
             _ => false
         });
     }
-}
\ No newline at end of file
+
+    fn leaf(handle: u64) -> Area {
+        Area::Container(Container::new(Dock::new(DockHandle(handle), "test"), Rect::default()))
+    }
+
+    /// Builds 2x2 grid where docks are added in column order: 1 = top-left, 2 = bottom-left,
+    /// 3 = top-right, 4 = bottom-right
+    fn grid_2x2() -> Area {
+        let left = Area::Split(Split::from_two(Direction::Horizontal, 0.5, SplitHandle(2), Rect::default(), leaf(1), leaf(2)));
+        let right = Area::Split(Split::from_two(Direction::Horizontal, 0.5, SplitHandle(3), Rect::default(), leaf(3), leaf(4)));
+        Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), left, right))
+    }
+
+    #[test]
+    fn test_simplify() {
+        let mut empty_container = Container::new(Dock::new(DockHandle(4), "test"), Rect::default());
+        empty_container.docks.clear();
+        let empty = Area::Container(empty_container);
+        let nested_same = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(2), Rect::default(), leaf(1), leaf(2)));
+        let single = Area::Split(Split::from_two(Direction::Horizontal, 0.5, SplitHandle(3), Rect::default(), leaf(3), empty.clone()));
+        let mut root = Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), nested_same, single);
+        root.append_child(1, empty).unwrap();
+        // Squeezed to almost nothing at the end
+        root.insert_child_after(2, leaf(5), 0.995).unwrap();
+        let mut area = Area::Split(root);
+
+        area.simplify();
+        let simplified = serde_json::to_string(&area).unwrap();
+        match area {
+            Area::Split(ref s) => {
+                assert_eq!(s.handle, SplitHandle(1));
+                assert_eq!(s.direction, Direction::Vertical);
+                assert_eq!(s.children.len(), 3);
+                let tabs: Vec<Vec<DockHandle>> = s.children.iter().map(|child| match *child {
+                    Area::Container(ref c) => c.docks.iter().map(|dock| dock.handle).collect(),
+                    _ => panic!("Expected container"),
+                }).collect();
+                assert_eq!(tabs, vec![vec![DockHandle(1)], vec![DockHandle(2)], vec![DockHandle(3), DockHandle(5)]]);
+                assert_eq!(s.ratios, vec![0.25, 0.5, 1.0]);
+            },
+            _ => panic!("Expected split at root"),
+        }
+        assert_eq!(area.docks_in_visual_order(), vec![DockHandle(1), DockHandle(2), DockHandle(3), DockHandle(5)]);
+
+        assert!(!area.simplify());
+        assert_eq!(serde_json::to_string(&area).unwrap(), simplified);
+    }
+
+    #[test]
+    fn test_dock_borrow() {
+        let area = grid_2x2();
+        assert_eq!(area.dock(DockHandle(3)).map(|dock| dock.plugin_name.as_str()), Some("test"));
+        assert!(area.dock(DockHandle(7)).is_none());
+
+        let names: Vec<&str> = area.visible_docks().iter()
+            .filter_map(|visible| area.dock(visible.handle))
+            .map(|dock| dock.plugin_name.as_str())
+            .collect();
+        assert_eq!(names.len(), 4);
+    }
+
+    #[test]
+    fn test_node_counts() {
+        let mut area = grid_2x2();
+        if let Area::Split(ref mut s) = area {
+            if let Area::Split(ref mut left) = s.children[0] {
+                if let Area::Container(ref mut c) = left.children[0] {
+                    c.docks.push(Dock::new(DockHandle(5), "tab"));
+                }
+            }
+        }
+        assert_eq!(area.node_counts(), NodeCounts { splits: 3, containers: 4, docks: 5, floating: 0 });
+        assert_eq!(leaf(1).node_counts(), NodeCounts { splits: 0, containers: 1, docks: 1, floating: 0 });
+    }
+
+    #[test]
+    fn test_leaves_with_depth() {
+        assert_eq!(leaf(1).leaves_with_depth().map(|(_, _, depth)| depth).collect::<Vec<_>>(), vec![0]);
+
+        let nested = Area::Split(Split::from_two(Direction::Horizontal, 0.5, SplitHandle(2), Rect::default(), leaf(2), leaf(3)));
+        let area = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), leaf(1), nested));
+        let leaves: Vec<(DockHandle, Rect, usize)> = area.leaves_with_depth().collect();
+        assert_eq!(leaves.len(), 3);
+        assert_eq!((leaves[0].0, leaves[0].2), (DockHandle(1), 1));
+        assert_eq!((leaves[1].0, leaves[1].2), (DockHandle(2), 2));
+        assert_eq!((leaves[2].0, leaves[2].2), (DockHandle(3), 2));
+        assert_eq!(leaves[2].1, Rect::new(50.0, 50.0, 50.0, 50.0));
+    }
+
+    #[test]
+    fn test_simplify_empty_child_split() {
+        let mut inner = Split::from_two(Direction::Vertical, 0.5, SplitHandle(2), Rect::default(), leaf(1), leaf(2));
+        inner.children.clear();
+        inner.ratios.clear();
+        let mut outer = Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), Area::Split(inner), leaf(3));
+        outer.remove_child(1);
+        let serialized = serde_json::to_string(&Area::Split(outer)).unwrap();
+        assert!(serialized.contains(r#""children":[]"#));
+
+        let mut area: Area = serde_json::from_str(&serialized).unwrap();
+        assert!(area.simplify());
+        match area {
+            Area::Split(ref s) => {
+                assert_eq!(s.handle, SplitHandle(1));
+                assert!(s.children.is_empty());
+                assert!(s.ratios.is_empty());
+            }
+            _ => panic!("root should stay a split"),
+        }
+        assert!(!area.simplify());
+    }
+
+    #[test]
+    fn test_visually_equivalent() {
+        let nested = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(2), Rect::default(), leaf(2), leaf(3)));
+        let area = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 90.0, 100.0), leaf(1), nested));
+        let mut flattened = area.clone();
+        flattened.simplify();
+
+        assert!(area.node_counts() != flattened.node_counts());
+        assert!(area.visually_equivalent(&flattened, 0.001));
+        assert!(flattened.visually_equivalent(&area, 0.001));
+        assert!(!area.visually_equivalent(&grid_2x2(), 0.001));
+    }
+
+    #[test]
+    fn test_nearest_dock_at_pos() {
+        let area = grid_2x2();
+        assert_eq!(area.get_dock_handle_at_pos((150.0, 10.0)), None);
+        assert_eq!(area.nearest_dock_at_pos((150.0, 10.0)), Some(DockHandle(3)));
+        assert_eq!(area.nearest_dock_at_pos((-5.0, 90.0)), Some(DockHandle(2)));
+        assert_eq!(area.nearest_dock_at_pos((60.0, 60.0)), Some(DockHandle(4)));
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut split = Split::from_two(Direction::Horizontal, 0.2, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), leaf(1), leaf(2));
+        split.append_child(1, leaf(3)).unwrap();
+        let mut area = Area::Split(split);
+        assert_eq!(area.docks_in_visual_order(), vec![DockHandle(1), DockHandle(3), DockHandle(2)]);
+        let heights: Vec<f32> = area.leaves_with_depth().map(|(_, rect, _)| rect.height).collect();
+
+        area.mirror(Direction::Vertical);
+        assert_eq!(area.docks_in_visual_order(), vec![DockHandle(1), DockHandle(3), DockHandle(2)]);
+
+        area.mirror(Direction::Horizontal);
+        assert_eq!(area.docks_in_visual_order(), vec![DockHandle(2), DockHandle(3), DockHandle(1)]);
+        let mirrored: Vec<(DockHandle, Rect, usize)> = area.leaves_with_depth().collect();
+        assert!(mirrored[0].1.approx_eq(&Rect::new(0.0, 0.0, 100.0, heights[2]), 0.001));
+        assert!(mirrored[1].1.approx_eq(&Rect::new(0.0, heights[2], 100.0, heights[1]), 0.001));
+        assert!(mirrored[2].1.approx_eq(&Rect::new(0.0, 100.0 - heights[0], 100.0, heights[0]), 0.001));
+    }
+
+    #[test]
+    fn test_equalize_direction() {
+        let inner_vertical = Area::Split(Split::from_two(Direction::Vertical, 0.2, SplitHandle(2), Rect::default(), leaf(2), leaf(3)));
+        let inner_horizontal = Split::from_two(Direction::Horizontal, 0.3, SplitHandle(3), Rect::default(), leaf(4), inner_vertical);
+        let mut area = Area::Split(Split::from_two(Direction::Vertical, 0.7, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), leaf(1), Area::Split(inner_horizontal)));
+
+        area.equalize_direction(Direction::Vertical);
+
+        let root = match area {
+            Area::Split(ref s) => s,
+            _ => panic!("Expected split"),
+        };
+        assert_eq!(root.ratios, vec![0.5, 1.0]);
+        let horizontal = match root.children[1] {
+            Area::Split(ref s) => s,
+            _ => panic!("Expected split"),
+        };
+        assert_eq!(horizontal.ratios, vec![0.3, 1.0]);
+        match horizontal.children[1] {
+            Area::Split(ref s) => assert_eq!(s.ratios, vec![0.5, 1.0]),
+            _ => panic!("Expected split"),
+        }
+    }
+
+    #[test]
+    fn test_common_ancestor() {
+        let deep = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(4), Rect::default(), leaf(4), leaf(5)));
+        let mut area = grid_2x2();
+        if let Area::Split(ref mut s) = area {
+            if let Area::Split(ref mut right) = s.children[1] {
+                right.replace_child(1, deep);
+            }
+        }
+        assert_eq!(area.common_ancestor(DockHandle(4), DockHandle(5)), Some(SplitHandle(4)));
+        assert_eq!(area.common_ancestor(DockHandle(3), DockHandle(5)), Some(SplitHandle(3)));
+        assert_eq!(area.common_ancestor(DockHandle(2), DockHandle(5)), Some(SplitHandle(1)));
+        assert_eq!(area.common_ancestor(DockHandle(1), DockHandle(2)), Some(SplitHandle(2)));
+        assert_eq!(area.common_ancestor(DockHandle(1), DockHandle(9)), None);
+    }
+
+    #[test]
+    fn test_docks_in_visual_order() {
+        let area = grid_2x2();
+        assert_eq!(area.docks_in_visual_order(), vec![DockHandle(1), DockHandle(3), DockHandle(2), DockHandle(4)]);
+    }
+
+    #[test]
+    fn test_replace_dock() {
+        let mut tabs = Container::new(Dock::new(DockHandle(1), "source"), Rect::default());
+        tabs.docks.push(Dock::new(DockHandle(2), "memory"));
+        tabs.docks.push(Dock::new(DockHandle(3), "registers"));
+        tabs.set_active_tab(1);
+        let mut area = Area::Split(Split::from_two(
+            Direction::Vertical,
+            0.5,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Area::Container(tabs),
+            leaf(4)));
+
+        let old = area.replace_dock(DockHandle(2), Dock::new(DockHandle(5), "memory")).unwrap();
+        assert_eq!(old.handle, DockHandle(2));
+        assert!(area.replace_dock(DockHandle(2), Dock::new(DockHandle(6), "test")).is_none());
+
+        let container = area.find_container_by_dock_handle(DockHandle(5)).unwrap();
+        let handles: Vec<u64> = container.docks.iter().map(|dock| dock.handle.0).collect();
+        assert_eq!(handles, vec![1, 5, 3]);
+        assert_eq!(container.active_dock, 1);
+        assert_eq!(container.rect, Rect::new(0.0, 0.0, 50.0, 100.0));
+        assert_eq!(area.find_container_by_dock_handle(DockHandle(4)).unwrap().rect, Rect::new(50.0, 0.0, 50.0, 100.0));
+    }
+
+    #[test]
+    fn test_pack_atlas() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(100.0, 50.0, 400.0, 200.0));
+        let atlas = area.pack_atlas((1024.0, 1024.0));
+
+        assert_eq!(atlas.len(), 4);
+        assert_eq!(atlas[&DockHandle(1)], Rect::new(0.0, 0.0, 512.0, 512.0));
+        assert_eq!(atlas[&DockHandle(4)], Rect::new(512.0, 512.0, 512.0, 512.0));
+        for rect in atlas.values() {
+            assert!(rect.x >= 0.0 && rect.y >= 0.0);
+            assert!(rect.x + rect.width <= 1024.0 && rect.y + rect.height <= 1024.0);
+        }
+        assert!(atlas[&DockHandle(1)].x < atlas[&DockHandle(3)].x);
+        assert!(atlas[&DockHandle(1)].y < atlas[&DockHandle(2)].y);
+    }
+
+    #[test]
+    fn test_area_fractions() {
+        let mut area = grid_2x2();
+        if let Area::Split(ref mut root) = area {
+            root.ratios[0] = 0.6;
+            if let Area::Split(ref mut right) = root.children[1] {
+                if let Area::Container(ref mut c) = right.children[0] {
+                    c.docks.push(Dock::new(DockHandle(5), "test"));
+                }
+            }
+        }
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let fractions = area.area_fractions();
+
+        assert_eq!(fractions.len(), 5);
+        assert!((fractions[&DockHandle(1)] - 0.3).abs() < 0.0001);
+        assert!((fractions[&DockHandle(3)] - 0.2).abs() < 0.0001);
+        assert_eq!(fractions[&DockHandle(5)], 0.0);
+        let sum = fractions.values().fold(0.0, |sum, fraction| sum + fraction);
+        assert!((sum - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_wrap_child_in_split() {
+        let mut area = grid_2x2();
+        let rect = area.find_container_by_dock_handle(DockHandle(3)).unwrap().rect;
+
+        assert!(area.wrap_child_in_split(DockHandle(3), Direction::Vertical, SplitHandle(7)));
+        assert!(!area.wrap_child_in_split(DockHandle(9), Direction::Vertical, SplitHandle(8)));
+        assert_eq!(area.split_path_to_dock(DockHandle(3)), Some(vec![SplitHandle(1), SplitHandle(3), SplitHandle(7)]));
+        let wrapper = area.find_split_by_handle(SplitHandle(7)).unwrap();
+        assert_eq!(wrapper.rect, rect);
+        assert_eq!(wrapper.children.len(), 1);
+        assert_eq!(wrapper.ratios, vec![1.0]);
+        assert_eq!(wrapper.children[0].get_rect(), rect);
+    }
+
+    #[test]
+    fn test_sizers_in_rect() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        assert_eq!(area.all_sizers(8.0).len(), 3);
+
+        // Only the sizer of the left column is around (25, 50)
+        let sizers = area.sizers_in_rect(&Rect::new(20.0, 48.0, 10.0, 4.0), 8.0);
+        assert_eq!(sizers.len(), 1);
+        assert_eq!(sizers[0].handle, SplitHandle(2));
+        assert_eq!(sizers[0].index, 0);
+        assert_eq!(sizers[0].direction, Direction::Horizontal);
+        assert_eq!(sizers[0].rect, Rect::new(0.0, 46.0, 50.0, 8.0));
+
+        // Center touches all of them
+        assert_eq!(area.sizers_in_rect(&Rect::new(48.0, 48.0, 4.0, 4.0), 8.0).len(), 3);
+        assert!(area.sizers_in_rect(&Rect::new(10.0, 10.0, 10.0, 10.0), 8.0).is_empty());
+    }
+
+    #[test]
+    fn test_regroup() {
+        let mut tabs = Container::new(Dock::new(DockHandle(5), "a"), Rect::default());
+        tabs.docks.push(Dock::new(DockHandle(6), "b"));
+        tabs.docks.push(Dock::new(DockHandle(7), "c"));
+        let mut area = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), leaf(1), Area::Container(tabs)));
+
+        if let Area::Split(ref mut root) = area {
+            let exploded = match root.children[1] {
+                Area::Container(ref c) => c.explode_to_split(Direction::Horizontal, SplitHandle(2)),
+                _ => panic!("expected container"),
+            };
+            root.replace_child(1, Area::Split(exploded));
+        }
+        assert_eq!(area.node_counts().containers, 4);
+        assert_eq!(area.dock(DockHandle(7)).unwrap().tab_group_id, Some(5));
+        assert!(!area.regroup(1));
+
+        assert!(area.regroup(5));
+        assert_eq!(area.node_counts(), NodeCounts { splits: 1, containers: 2, docks: 4, floating: 0 });
+        let container = area.find_container_by_dock_handle(DockHandle(5)).unwrap();
+        let handles: Vec<u64> = container.docks.iter().map(|dock| dock.handle.0).collect();
+        assert_eq!(handles, vec![5, 6, 7]);
+        assert_eq!(container.rect, Rect::new(50.0, 0.0, 50.0, 100.0));
+    }
+
+    #[test]
+    fn test_enclosing_split_of_rect() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+
+        // Spans docks 1 and 2 of the left column
+        assert_eq!(area.enclosing_split_of_rect(&Rect::new(10.0, 30.0, 20.0, 40.0)), Some(SplitHandle(2)));
+        // Spans docks 2 and 4 from different columns
+        assert_eq!(area.enclosing_split_of_rect(&Rect::new(30.0, 60.0, 40.0, 20.0)), Some(SplitHandle(1)));
+        // Inside a single container the lowest split above it is returned
+        assert_eq!(area.enclosing_split_of_rect(&Rect::new(60.0, 10.0, 10.0, 10.0)), Some(SplitHandle(3)));
+        assert_eq!(area.enclosing_split_of_rect(&Rect::new(50.0, 50.0, 100.0, 10.0)), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut tabs = Container::new(Dock::new(DockHandle(2), "memory"), Rect::default());
+        tabs.docks.push(Dock::new(DockHandle(3), "registers"));
+        let area = Area::Split(Split::from_two(Direction::Vertical, 0.25, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0),
+                                               Area::Container(Container::new(Dock::new(DockHandle(1), "source"), Rect::default())),
+                                               Area::Container(tabs)));
+        let dot = area.to_dot();
+
+        assert!(dot.starts_with("digraph layout {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("split_1 [shape=box, label=\"Vertical\\n0.25, 1\"];"));
+        assert!(dot.contains("dock_1 [label=\"source\\n#1\"];"));
+        assert!(dot.contains("split_1 -> dock_1;"));
+        assert!(dot.contains("split_1 -> tabs_2;"));
+        assert!(dot.contains("tabs_2 -> dock_2;"));
+        assert!(dot.contains("tabs_2 -> dock_3;"));
+        assert!(!dot.contains("split_1 -> dock_2;"));
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let svg = area.to_svg((200.0, 100.0));
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"100\" viewBox=\"0 0 200 100\">"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect ").count(), 4);
+        assert_eq!(svg.matches("<line ").count(), 3);
+        assert!(svg.contains("<rect x=\"100\" y=\"50\" width=\"100\" height=\"50\" fill=\"none\" stroke=\"black\"/>"));
+        assert!(svg.contains(">test #4</text>"));
+    }
+
+    #[test]
+    fn test_rebalance_to_aspect() {
+        let mut area = Area::Split(Split::from_children(Direction::Horizontal, SplitHandle(1), Rect::new(0.0, 0.0, 300.0, 300.0),
+                                                         vec![leaf(1), leaf(2), leaf(3)]));
+        area.rebalance_to_aspect(1.0);
+        assert_eq!(area.get_rect(), Rect::new(0.0, 0.0, 300.0, 300.0));
+
+        // Very wide root would turn horizontally stacked docks into 1000x33 slivers
+        area.update_rect(Rect::new(0.0, 0.0, 1000.0, 100.0));
+        area.rebalance_to_aspect(1.0);
+        let container = area.find_container_by_dock_handle(DockHandle(2)).unwrap();
+        assert!((container.rect.width - 1000.0 / 3.0).abs() < 0.001);
+        assert_eq!(container.rect.height, 100.0);
+
+        let mut grid = grid_2x2();
+        grid.update_rect(Rect::new(0.0, 0.0, 2000.0, 100.0));
+        grid.rebalance_to_aspect(1.0);
+        for (_, rect, _) in grid.leaves_with_depth() {
+            assert!(rect.width / rect.height < 6.0);
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let snapshot: LayoutSnapshot = area.snapshot();
+        assert_eq!(snapshot.leaves.len(), 4);
+        assert_eq!(snapshot.leaves[0], (DockHandle(1), Rect::new(0.0, 0.0, 50.0, 50.0)));
+
+        area.find_split_by_handle(SplitHandle(1)).unwrap().change_ratio(0, (-20.0, 0.0));
+        let resized = area.snapshot();
+        assert_eq!(resized.structure_hash, snapshot.structure_hash);
+        assert!(resized.leaves != snapshot.leaves);
+
+        area.wrap_child_in_split(DockHandle(4), Direction::Vertical, SplitHandle(5));
+        area.replace_dock(DockHandle(1), Dock::new(DockHandle(9), "test"));
+        assert!(area.snapshot().structure_hash != snapshot.structure_hash);
+
+        // Snapshot still shows the layout as it was
+        assert_eq!(snapshot.leaves[0], (DockHandle(1), Rect::new(0.0, 0.0, 50.0, 50.0)));
+        assert_eq!(snapshot.leaves[3], (DockHandle(4), Rect::new(50.0, 50.0, 50.0, 50.0)));
+    }
+
+    #[test]
+    fn test_nearest_drop_target() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        if let Area::Split(ref mut s) = area {
+            if let Area::Split(ref mut column) = s.children[0] {
+                if let Area::Container(ref mut c) = column.children[0] {
+                    c.accepts_drops = false;
+                }
+            }
+        }
+
+        // Over the header of the locked container, the one below is closest
+        let (target, rect) = area.nearest_drop_target((10.0, 25.0), DockHandle(4)).unwrap();
+        assert_eq!(target, DropTarget::Dock(DockHandle(2)));
+        assert_eq!(rect, Rect::new(0.0, 50.0, 50.0, 50.0));
+
+        // Legal target under the pointer is used as is
+        let (target, _) = area.nearest_drop_target((60.0, 5.0), DockHandle(4)).unwrap();
+        assert_eq!(target, DropTarget::Dock(DockHandle(3)));
+
+        // Dock isn't dropped on its own container
+        let (target, _) = area.nearest_drop_target((60.0, 55.0), DockHandle(4)).unwrap();
+        assert!(target != DropTarget::Dock(DockHandle(4)));
+    }
+
+    #[test]
+    fn test_collapse_split_to_tabs() {
+        let mut split = Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 90.0, 100.0), leaf(1), leaf(2));
+        split.insert_child_after(1, leaf(3), 0.5).unwrap();
+        let mut area = Area::Split(split);
+        assert!(!area.collapse_split_to_tabs(SplitHandle(7)));
+        assert!(area.collapse_split_to_tabs(SplitHandle(1)));
+        match area {
+            Area::Container(ref c) => {
+                let order: Vec<DockHandle> = c.docks.iter().map(|dock| dock.handle).collect();
+                assert_eq!(order, vec![DockHandle(1), DockHandle(2), DockHandle(3)]);
+                assert_eq!(c.active_dock, 0);
+                assert_eq!(c.rect, Rect::new(0.0, 0.0, 90.0, 100.0));
+            },
+            _ => panic!("Expected container"),
+        }
+
+        // Nested split is flattened
+        let mut area = grid_2x2();
+        assert!(area.collapse_split_to_tabs(SplitHandle(1)));
+        let order: Vec<DockHandle> = area.docks_mut().iter().map(|dock| dock.handle).collect();
+        assert_eq!(order, vec![DockHandle(1), DockHandle(2), DockHandle(3), DockHandle(4)]);
+    }
+
+    #[test]
+    fn test_elevated_render_order() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let rect = area.find_container_by_dock_handle(DockHandle(2)).unwrap().rect;
+        area.find_container_mut(DockHandle(2)).unwrap().elevated = true;
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+
+        let order: Vec<DockHandle> = area.visible_docks().iter().map(|dock| dock.handle).collect();
+        assert_eq!(order, vec![DockHandle(1), DockHandle(3), DockHandle(4), DockHandle(2)]);
+        assert_eq!(area.find_container_by_dock_handle(DockHandle(2)).unwrap().rect, rect);
+        assert_eq!(area.get_dock_handle_at_pos((60.0, 60.0)), Some(DockHandle(4)));
+    }
+
+    #[test]
+    fn test_docks_under_split() {
+        let mut area = grid_2x2();
+        area.wrap_child_in_split(DockHandle(4), Direction::Vertical, SplitHandle(5));
+        area.find_container_mut(DockHandle(4)).unwrap().docks.push(Dock::new(DockHandle(6), "test"));
+
+        assert_eq!(area.docks_under_split(SplitHandle(3)), vec![DockHandle(3), DockHandle(4), DockHandle(6)]);
+        assert_eq!(area.docks_under_split(SplitHandle(1)).len(), 5);
+        assert!(area.docks_under_split(SplitHandle(9)).is_empty());
+    }
+
+    #[test]
+    fn test_to_ratio_tree() {
+        let mut tabs = Container::new(Dock::new(DockHandle(2), "memory"), Rect::default());
+        tabs.docks.push(Dock::new(DockHandle(3), "registers"));
+        let right = Area::Split(Split::from_two(Direction::Horizontal, 0.5, SplitHandle(2), Rect::default(), Area::Container(tabs), leaf(4)));
+        let area = Area::Split(Split::from_two(Direction::Vertical, 0.25, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0),
+                                               Area::Container(Container::new(Dock::new(DockHandle(1), "source"), Rect::default())),
+                                               right));
+        let dock = |handle, name: &str| RatioNode::Dock { handle: DockHandle(handle), name: name.to_owned() };
+
+        let expected = RatioNode::Split {
+            direction: Direction::Vertical,
+            children: vec![
+                RatioChild { ratio: 0.25, node: dock(1, "source") },
+                RatioChild { ratio: 0.75, node: RatioNode::Split {
+                    direction: Direction::Horizontal,
+                    children: vec![
+                        RatioChild { ratio: 0.5, node: RatioNode::Tabs(vec![dock(2, "memory"), dock(3, "registers")]) },
+                        RatioChild { ratio: 0.5, node: dock(4, "test") },
+                    ],
+                }},
+            ],
+        };
+        assert_eq!(area.to_ratio_tree(), expected);
+        assert_eq!(serde_json::to_string(&area.to_ratio_tree()).unwrap(),
+                   concat!(r#"{"direction":"Vertical","children":[{"ratio":0.25,"node":{"handle":1,"name":"source"}},"#,
+                           r#"{"ratio":0.75,"node":{"direction":"Horizontal","children":[{"ratio":0.5,"node":{"tabs":["#,
+                           r#"{"handle":2,"name":"memory"},{"handle":3,"name":"registers"}]}},"#,
+                           r#"{"ratio":0.5,"node":{"handle":4,"name":"test"}}]}}]}"#));
+    }
+
+    #[test]
+    fn test_add_spanning() {
+        let row = Area::Split(Split::from_children(Direction::Vertical, SplitHandle(2), Rect::default(), vec![leaf(1), leaf(2), leaf(3)]));
+        let mut area = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::new(0.0, 0.0, 300.0, 200.0), row, leaf(4)));
+        area.update_rect(Rect::new(0.0, 0.0, 300.0, 200.0));
+        assert!(!area.add_spanning(SplitHandle(9), Dock::new(DockHandle(5), "header"), Edge::Top, 0.25, SplitHandle(3)));
+        assert!(area.add_spanning(SplitHandle(2), Dock::new(DockHandle(5), "header"), Edge::Top, 0.25, SplitHandle(3)));
+
+        let wrapper = area.split(SplitHandle(3)).unwrap();
+        assert_eq!(wrapper.direction, Direction::Horizontal);
+        assert_eq!(wrapper.rect, Rect::new(0.0, 0.0, 150.0, 200.0));
+        match wrapper.children[1] {
+            Area::Split(ref s) => assert_eq!(s.handle, SplitHandle(2)),
+            _ => panic!("Expected the original split below the header"),
+        }
+        let header = area.find_container_by_dock_handle(DockHandle(5)).unwrap().rect;
+        assert_eq!(header, Rect::new(0.0, 0.0, 150.0, 50.0));
+        for handle in 1..4 {
+            let rect = area.find_container_by_dock_handle(DockHandle(handle)).unwrap().rect;
+            assert_eq!(rect.y, 50.0);
+            assert_eq!(rect.height, 150.0);
+        }
+    }
+
+    #[test]
+    fn test_resizable_edges() {
+        let area = grid_2x2();
+        assert_eq!(area.resizable_edges(DockHandle(1)), EdgeSet { left: false, right: true, top: false, bottom: true });
+        assert_eq!(area.resizable_edges(DockHandle(4)), EdgeSet { left: true, right: false, top: true, bottom: false });
+        assert!(area.resizable_edges(DockHandle(9)).is_empty());
+        assert!(leaf(1).resizable_edges(DockHandle(1)).is_empty());
+    }
+
+    #[test]
+    fn test_neighbor_dock() {
+        let right = Area::Split(Split::from_two(Direction::Horizontal, 0.25, SplitHandle(2), Rect::default(), leaf(2), leaf(3)));
+        let mut area = Area::Split(Split::from_two(Direction::Vertical, 0.5, SplitHandle(1), Rect::default(), leaf(1), right));
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+
+        // Dock 3 shares more of the border than dock 2
+        assert_eq!(area.neighbor_dock(DockHandle(1), Edge::Right), Some(DockHandle(3)));
+        assert_eq!(area.neighbor_dock(DockHandle(2), Edge::Left), Some(DockHandle(1)));
+        assert_eq!(area.neighbor_dock(DockHandle(2), Edge::Bottom), Some(DockHandle(3)));
+        assert_eq!(area.neighbor_dock(DockHandle(1), Edge::Left), None);
+        assert_eq!(area.neighbor_dock(DockHandle(9), Edge::Left), None);
+    }
+
+    #[test]
+    fn test_sizer_count() {
+        let mut area = grid_2x2();
+        area.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        area.find_split_by_handle(SplitHandle(3)).unwrap().append_child(0, leaf(5)).unwrap();
+        assert_eq!(area.sizer_count(), 4);
+        assert_eq!(area.sizer_count(), area.all_sizers(8.0).len());
+
+        area.find_split_by_handle(SplitHandle(3)).unwrap().set_sizer_locked(1, true);
+        assert_eq!(area.sizer_count(), 3);
+        assert_eq!(area.all_sizers(8.0).len(), 3);
+        let split = area.find_split_by_handle(SplitHandle(3)).unwrap();
+        let ratio = split.ratios[1];
+        assert!(!split.change_ratio(1, (0.0, 10.0)));
+        assert_eq!(split.ratios[1], ratio);
+        split.set_sizer_locked(1, false);
+        assert_eq!(area.sizer_count(), 4);
+    }
+}
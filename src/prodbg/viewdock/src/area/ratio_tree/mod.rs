@@ -0,0 +1,25 @@
+mod serialize;
+
+use dock::DockHandle;
+use rect::Direction;
+
+/// Minimal nested description of a layout for external tools. Holds only structure, relative
+/// sizes and dock names, no rects or other state.
+///
+/// Serializes to plain objects: `{"direction", "children": [{"ratio", "node"}]}` for splits,
+/// `{"handle", "name"}` for docks and `{"tabs": [...]}` for containers with several docks.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RatioNode {
+    Split { direction: Direction, children: Vec<RatioChild> },
+    Dock { handle: DockHandle, name: String },
+    /// Container with more than one dock. Holds `Dock` nodes in tab order
+    Tabs(Vec<RatioNode>),
+}
+
+/// Child of a split in a ratio tree
+#[derive(Debug, PartialEq, Clone)]
+pub struct RatioChild {
+    /// Share of the split the child takes. Ratios of all children of a split sum up to 1
+    pub ratio: f32,
+    pub node: RatioNode,
+}
@@ -0,0 +1,57 @@
+extern crate serde;
+use super::{RatioNode, RatioChild};
+use rect::Direction;
+
+// Serialization
+
+impl serde::ser::Serialize for RatioNode {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: serde::ser::Serializer {
+        serializer.serialize_struct("RatioNode", RatioNodeMapVisitor { value: self }).map(|_| ())
+    }
+}
+
+struct RatioNodeMapVisitor<'a> {
+    value: &'a RatioNode
+}
+
+impl<'a> serde::ser::MapVisitor for RatioNodeMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
+        match *self.value {
+            RatioNode::Split { ref direction, ref children } => {
+                // Plain string instead of the enum encoding so the format is easy to read elsewhere
+                let direction = match *direction {
+                    Direction::Vertical => "Vertical",
+                    Direction::Horizontal => "Horizontal",
+                };
+                serializer.serialize_struct_elt("direction", direction)?;
+                serializer.serialize_struct_elt("children", children)?;
+            },
+            RatioNode::Dock { ref handle, ref name } => {
+                serializer.serialize_struct_elt("handle", handle)?;
+                serializer.serialize_struct_elt("name", name)?;
+            },
+            RatioNode::Tabs(ref tabs) => {
+                serializer.serialize_struct_elt("tabs", tabs)?;
+            },
+        }
+        Ok(None)
+    }
+}
+
+impl serde::ser::Serialize for RatioChild {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: serde::ser::Serializer {
+        serializer.serialize_struct("RatioChild", RatioChildMapVisitor { value: self }).map(|_| ())
+    }
+}
+
+struct RatioChildMapVisitor<'a> {
+    value: &'a RatioChild
+}
+
+impl<'a> serde::ser::MapVisitor for RatioChildMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
+        serializer.serialize_struct_elt("ratio", self.value.ratio)?;
+        serializer.serialize_struct_elt("node", &self.value.node)?;
+        Ok(None)
+    }
+}
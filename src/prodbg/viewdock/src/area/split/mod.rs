@@ -1,8 +1,22 @@
 mod serialize;
 
-use super::{Area, DragTarget, DropTarget};
-use dock::DockHandle;
-use rect::{Rect, Direction};
+use super::{Area, Container, DragTarget, DropTarget, SplitStyle, SizerInfo};
+use dock::{Dock, DockHandle};
+use rect::{Rect, Direction, Edge};
+use {Error, ResultView};
+
+/// Smallest ratio a sizer can be dragged to
+pub const MIN_RATIO: f32 = 0.01;
+/// Largest ratio a sizer can be dragged to
+pub const MAX_RATIO: f32 = 0.99;
+/// Width (in pixels) of the band around split borders that grabs the sizer at DPI scale 1.0
+pub const SIZER_WIDTH: f32 = 8.0;
+/// Default distance (in pixels) a sizer held at its limit has to be dragged back before it moves
+pub const RESIZE_HYSTERESIS: f32 = 2.0;
+/// Rate at which velocity of an inertial resize decays (per second)
+pub const INERTIA_DECAY: f32 = 4.0;
+/// Inertial resize stops when the velocity (in pixels per second) drops below this
+pub const INERTIA_MIN_VELOCITY: f32 = 10.0;
 
 /// Handle to a split
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -21,6 +35,27 @@ pub struct Split {
     pub handle: SplitHandle,
     /// Area occupied by this split
     pub rect: Rect,
+    /// Rendering hints. Doesn't affect the layout
+    pub style: Option<SplitStyle>,
+    /// When children don't fit at their minimum sizes the split scrolls. This is how far (in
+    /// pixels) the content is scrolled. Not serialized.
+    pub scroll_offset: f32,
+    /// Layouts with fewer children in this split are rejected by `validate`
+    pub min_children: Option<usize>,
+    /// Children can't be added past this count. Callers should nest a new split instead.
+    pub max_children: Option<usize>,
+    /// Once a sizer is stopped at its limit it has to be dragged back this many pixels before it
+    /// moves again. Prevents jitter from tiny deltas around the limit.
+    pub hysteresis: f32,
+    /// Sizer held at its limit: index, true if at the lower limit and how far (as ratio) it has
+    /// been dragged back so far. Not serialized.
+    clamped_sizer: Option<(usize, bool, f32)>,
+    /// Sizer that keeps moving after a touch drag was released: index and velocity in pixels
+    /// per second. Not serialized.
+    inertia: Option<(usize, f32)>,
+    /// Indices of sizers that can't be dragged. Adjusted when children are added or removed so
+    /// locks stay on the same boundaries. Not serialized.
+    locked_sizers: Vec<usize>,
 }
 
 impl Split {
@@ -28,42 +63,297 @@ impl Split {
         let mut res = Split {
             children: vec!(first, second),
             ratios: vec!(ratio, 1.0),
-            direction: direction,
-            handle: handle,
-            rect: rect,
+            direction,
+            handle,
+            rect,
+            style: None,
+            scroll_offset: 0.0,
+            min_children: None,
+            max_children: None,
+            hysteresis: RESIZE_HYSTERESIS,
+            clamped_sizer: None,
+            inertia: None,
+            locked_sizers: Vec::new(),
         };
         res.update_children_sizes();
         return res;
     }
 
+    /// Creates split where all children get the same amount of space
+    pub fn from_children(direction: Direction, handle: SplitHandle, rect: Rect, children: Vec<Area>) -> Split {
+        let mut res = Split {
+            ratios: vec![1.0; children.len()],
+            children,
+            direction,
+            handle,
+            rect,
+            style: None,
+            scroll_offset: 0.0,
+            min_children: None,
+            max_children: None,
+            hysteresis: RESIZE_HYSTERESIS,
+            clamped_sizer: None,
+            inertia: None,
+            locked_sizers: Vec::new(),
+        };
+        res.equalize();
+        res
+    }
+
+    /// Reverses order of children keeping size of each child
+    pub fn reverse_children(&mut self) {
+        let mut previous = 0.0;
+        let mut spans: Vec<f32> = self.ratios.iter().map(|&ratio| {
+            let span = ratio - previous;
+            previous = ratio;
+            span
+        }).collect();
+        spans.reverse();
+        self.children.reverse();
+        let last_sizer = self.ratios.len() - 1;
+        for index in self.locked_sizers.iter_mut() {
+            *index = last_sizer - 1 - *index;
+        }
+        let mut sum = 0.0;
+        for (ratio, span) in self.ratios.iter_mut().zip(spans.iter()) {
+            sum += *span;
+            *ratio = sum;
+        }
+        if let Some(last) = self.ratios.last_mut() {
+            *last = 1.0;
+        }
+        self.update_children_sizes();
+    }
+
+    /// Gives all children the same amount of space
+    pub fn equalize(&mut self) {
+        let count = self.ratios.len();
+        for (i, ratio) in self.ratios.iter_mut().enumerate() {
+            *ratio = (i + 1) as f32 / count as f32;
+        }
+        self.update_children_sizes();
+    }
+
+    /// Changes direction of the split. With `remap` the ratios are recalculated from the current
+    /// pixel sizes of the children so the visual proportions carry over to the new axis, even
+    /// when the children are currently laid out at their minimum sizes. Otherwise the ratios are
+    /// kept as is.
+    pub fn set_direction(&mut self, direction: Direction, remap: bool) {
+        if remap {
+            let sizes: Vec<f32> = self.children.iter().map(|child| {
+                let rect = child.get_rect();
+                match self.direction {
+                    Direction::Vertical => rect.width,
+                    Direction::Horizontal => rect.height,
+                }
+            }).collect();
+            let total = sizes.iter().fold(0.0, |sum, size| sum + size);
+            if total > 0.0 {
+                let mut sum = 0.0;
+                for (ratio, size) in self.ratios.iter_mut().zip(sizes.iter()) {
+                    sum += *size;
+                    *ratio = sum / total;
+                }
+                if let Some(last) = self.ratios.last_mut() {
+                    *last = 1.0;
+                }
+            }
+        }
+        self.direction = direction;
+        self.scroll_offset = 0.0;
+        self.update_children_sizes();
+    }
+
+    /// Turns ratios into a valid set after they have been edited by hand or imported: ratios are
+    /// sorted and clamped into (0, 1], the last one is set to 1 and equal ratios are spaced out
+    /// so no child collapses. If the count doesn't match the children all get equal space.
+    pub fn repair_ratios(&mut self) {
+        let count = self.children.len();
+        if count == 0 {
+            return;
+        }
+        if self.ratios.len() != count {
+            self.ratios = vec![1.0; count];
+            self.equalize();
+            return;
+        }
+        let gap = MIN_RATIO.min(1.0 / count as f32);
+        for ratio in self.ratios.iter_mut() {
+            *ratio = if ratio.is_nan() { 1.0 } else { ratio.clamp(0.0, 1.0) };
+        }
+        self.ratios.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        self.ratios[count - 1] = 1.0;
+        let mut previous = 0.0;
+        for ratio in self.ratios.iter_mut() {
+            *ratio = ratio.max(previous + gap);
+            previous = *ratio;
+        }
+        let mut next = 1.0 + gap;
+        for ratio in self.ratios.iter_mut().rev() {
+            *ratio = ratio.min(next - gap);
+            next = *ratio;
+        }
+        self.update_children_sizes();
+    }
+
+    /// Length of the content along the split axis. Larger than the split itself when children
+    /// don't fit at their minimum sizes.
+    pub fn content_extent(&self) -> f32 {
+        let (_, length) = self.axis_start_and_length();
+        let min_sum = self.children.iter()
+            .fold(0.0, |sum, child| sum + child.min_extent(self.direction));
+        length.max(min_sum)
+    }
+
+    /// Scrolls overflowing content. Offset is clamped to the scrollable range
+    pub fn set_scroll_offset(&mut self, offset: f32) {
+        self.scroll_offset = offset;
+        self.update_children_sizes();
+    }
+
     fn update_children_sizes(&mut self) {
-        let rects = self.rect.split_by_direction(self.direction, &self.ratios);
+        let (_, length) = self.axis_start_and_length();
+        let extent = self.content_extent();
+        let rects = if extent > length {
+            self.scroll_offset = self.scroll_offset.max(0.0).min(extent - length);
+            let sizes: Vec<f32> = self.children.iter().map(|child| child.min_extent(self.direction)).collect();
+            self.rect.split_by_sizes(self.direction, -self.scroll_offset, &sizes)
+        } else {
+            self.scroll_offset = 0.0;
+            self.rect.split_by_direction(self.direction, &self.ratios)
+        };
         for (child, rect) in self.children.iter_mut().zip(rects.iter()) {
             child.update_rect(*rect);
         }
     }
 
     pub fn update_rect(&mut self, rect: Rect) {
-        self.rect = rect;
+        let fixed: Vec<bool> = self.children.iter().map(|child| child.is_fixed()).collect();
+        if fixed.iter().any(|&is_fixed| is_fixed) {
+            self.redistribute(rect, &fixed);
+        } else {
+            self.rect = rect;
+        }
         self.update_children_sizes();
     }
 
+    /// Changes rect of the split so the child at `preserve_index` keeps its current pixel size
+    /// along the split axis and the other children share the rest in their current proportions.
+    pub fn resize_preserving(&mut self, new_rect: Rect, preserve_index: usize) {
+        let fixed: Vec<bool> = (0..self.children.len()).map(|i| i == preserve_index).collect();
+        self.redistribute(new_rect, &fixed);
+        self.update_children_sizes();
+    }
+
+    /// Sets new rect and recalculates ratios so children marked in `fixed` keep their pixel size
+    /// and the others take all of the change in proportion to their sizes. When there isn't
+    /// enough space for the fixed children the others get nothing and the fixed ones shrink.
+    fn redistribute(&mut self, new_rect: Rect, fixed: &[bool]) {
+        let (_, old_length) = self.axis_start_and_length();
+        self.rect = new_rect;
+        let (_, new_length) = self.axis_start_and_length();
+        if old_length <= 0.0 || new_length <= 0.0 || old_length == new_length {
+            return;
+        }
+        let mut previous = 0.0;
+        let sizes: Vec<f32> = self.ratios.iter().map(|&ratio| {
+            let size = (ratio - previous) * old_length;
+            previous = ratio;
+            size
+        }).collect();
+        let (fixed_sum, flexible_sum) = sizes.iter().zip(fixed.iter())
+            .fold((0.0, 0.0), |(fixed_sum, flexible_sum), (&size, &is_fixed)| {
+                if is_fixed { (fixed_sum + size, flexible_sum) } else { (fixed_sum, flexible_sum + size) }
+            });
+        let flexible_count = fixed.iter().filter(|&&is_fixed| !is_fixed).count();
+        if flexible_count == 0 {
+            return;
+        }
+        let new_sizes: Vec<f32> = if new_length >= fixed_sum {
+            let space = new_length - fixed_sum;
+            sizes.iter().zip(fixed.iter()).map(|(&size, &is_fixed)| {
+                if is_fixed {
+                    size
+                } else if flexible_sum > 0.0 {
+                    size * space / flexible_sum
+                } else {
+                    space / flexible_count as f32
+                }
+            }).collect()
+        } else {
+            sizes.iter().zip(fixed.iter()).map(|(&size, &is_fixed)| {
+                if is_fixed { size * new_length / fixed_sum } else { 0.0 }
+            }).collect()
+        };
+        let mut sum = 0.0;
+        for (ratio, size) in self.ratios.iter_mut().zip(new_sizes.iter()) {
+            sum += *size;
+            *ratio = sum / new_length;
+        }
+        if let Some(last) = self.ratios.last_mut() {
+            *last = 1.0;
+        }
+    }
+
     fn get_child_at_pos(&self, pos: (f32, f32)) -> Option<&Area> {
         self.children.iter()
             .find(|child| child.get_rect().point_is_inside(pos))
     }
 
-    pub fn get_drag_target_at_pos(&self, pos: (f32, f32)) -> Option<DragTarget> {
-        let sizer_rects = self.rect.area_around_splits(self.direction, &self.ratios[0..self.ratios.len() - 1], 8.0);
+    pub fn get_drag_target_at_pos(&self, pos: (f32, f32), sizer_width: f32) -> Option<DragTarget> {
+        let sizer_rects = self.rect.area_around_splits(self.direction, &self.ratios[0..self.ratios.len() - 1], sizer_width);
         return sizer_rects.iter().enumerate()
-            .find(|&(_, rect)| rect.point_is_inside(pos))
+            .find(|&(i, rect)| rect.point_is_inside(pos) && !self.is_sizer_locked(i))
             .map(|(i, _)| DragTarget::SplitSizer(self.handle, i, self.direction))
             .or_else(|| {
                 self.get_child_at_pos(pos)
-                    .and_then(|child| child.get_drag_target_at_pos(pos))
+                    .and_then(|child| child.get_drag_target_at_pos(pos, sizer_width))
             });
     }
 
+    /// Locks or unlocks sizer at `index`. Locked sizers aren't reported by `sizers` or
+    /// `get_drag_target_at_pos` and `change_ratio` doesn't move them.
+    pub fn set_sizer_locked(&mut self, index: usize, locked: bool) {
+        self.locked_sizers.retain(|&i| i != index);
+        if locked {
+            self.locked_sizers.push(index);
+        }
+    }
+
+    pub fn is_sizer_locked(&self, index: usize) -> bool {
+        self.locked_sizers.contains(&index)
+    }
+
+    /// Moves locks of sizers at `index` and after by `count` when new sizers are inserted at `index`
+    fn shift_locked_sizers(&mut self, index: usize, count: usize) {
+        for i in self.locked_sizers.iter_mut().filter(|i| **i >= index) {
+            *i += count;
+        }
+    }
+
+    /// Drops lock of sizer at `index` that was removed and moves locks after it back by one
+    fn remove_locked_sizer(&mut self, index: usize) {
+        self.locked_sizers.retain(|&i| i != index);
+        for i in self.locked_sizers.iter_mut().filter(|i| **i > index) {
+            *i -= 1;
+        }
+    }
+
+    /// Returns draggable sizers between the children of this split (not including nested splits)
+    pub fn sizers(&self, sizer_width: f32) -> Vec<SizerInfo> {
+        self.rect.area_around_splits(self.direction, &self.ratios[0..self.ratios.len() - 1], sizer_width)
+            .into_iter()
+            .enumerate()
+            .filter(|&(index, _)| !self.is_sizer_locked(index))
+            .map(|(index, rect)| SizerInfo {
+                handle: self.handle,
+                index,
+                direction: self.direction,
+                rect,
+            })
+            .collect()
+    }
+
     pub fn get_drop_target_at_pos(&self, pos: (f32, f32)) -> Option<DropTarget> {
         self.get_child_at_pos(pos)
             .and_then(|child| child.get_drop_target_at_pos(pos))
@@ -76,20 +366,244 @@ impl Split {
         }
     }
 
-    pub fn change_ratio(&mut self, index: usize, delta: (f32, f32)) {
-        let scale = Self::map_rect_to_delta(self, delta);
-        let mut res = self.ratios[index] + scale;
+    fn axis_start_and_length(&self) -> (f32, f32) {
+        match self.direction {
+            Direction::Vertical => (self.rect.x, self.rect.width),
+            Direction::Horizontal => (self.rect.y, self.rect.height),
+        }
+    }
+
+    /// Range of ratios the sizer at `index` can take without going past `MIN_RATIO`/`MAX_RATIO`
+    /// or making adjacent children smaller than their minimum size. If constraints can't be
+    /// satisfied the current ratio is returned as both ends.
+    fn ratio_range(&self, index: usize) -> (f32, f32) {
+        let (_, length) = self.axis_start_and_length();
+        let previous = match index {
+            0 => 0.0,
+            _ => self.ratios[index - 1],
+        };
+        let next = self.ratios[index + 1];
+        let (min_before, min_after) = if length > 0.0 {
+            (self.children[index].min_extent(self.direction) / length,
+             self.children[index + 1].min_extent(self.direction) / length)
+        } else {
+            (0.0, 0.0)
+        };
+        let min = MIN_RATIO.max(previous + min_before);
+        let max = MAX_RATIO.min(next - min_after);
+        if min > max {
+            (self.ratios[index], self.ratios[index])
+        } else {
+            (min, max)
+        }
+    }
+
+    /// Returns minimum and maximum pixel position the sizer at `index` can be dragged to.
+    pub fn sizer_range(&self, index: usize) -> (f32, f32) {
+        let (start, length) = self.axis_start_and_length();
+        let (min, max) = self.ratio_range(index);
+        (start + min * length, start + max * length)
+    }
+
+    /// Returns towards which side the sizer at `index` can't be moved any further because of
+    /// `MIN_RATIO`/`MAX_RATIO` or the minimum sizes of the adjacent children. Left/Top means it
+    /// can't move towards the start of the split and Right/Bottom towards the end. If it can't
+    /// move at all the start side is reported.
+    pub fn sizer_at_limit(&self, index: usize) -> Option<Edge> {
+        let (min, max) = self.ratio_range(index);
+        let ratio = self.ratios[index];
+        let epsilon = 0.00001;
+        if ratio <= min + epsilon {
+            Some(match self.direction {
+                Direction::Vertical => Edge::Left,
+                Direction::Horizontal => Edge::Top,
+            })
+        } else if ratio >= max - epsilon {
+            Some(match self.direction {
+                Direction::Vertical => Edge::Right,
+                Direction::Horizontal => Edge::Bottom,
+            })
+        } else {
+            None
+        }
+    }
 
-        if res < 0.01 {
-            res = 0.01;
+    /// Moves sizer at `index` by `delta` (in pixels). Returns false and does nothing if the
+    /// ratio wouldn't change, e.g. when delta is perpendicular to the split axis.
+    pub fn change_ratio(&mut self, index: usize, delta: (f32, f32)) -> bool {
+        self.inertia = None;
+        let mut scale = Self::map_rect_to_delta(self, delta);
+        if scale == 0.0 || self.is_sizer_locked(index) {
+            return false;
+        }
+        if let Some((clamped_index, at_min, pulled)) = self.clamped_sizer {
+            // The limit may have moved since, e.g. when the split was resized
+            let (min, max) = self.ratio_range(index);
+            let epsilon = 0.00001;
+            let held = if at_min {
+                self.ratios[index] <= min + epsilon
+            } else {
+                self.ratios[index] >= max - epsilon
+            };
+            if clamped_index == index && held {
+                let away = if at_min { scale > 0.0 } else { scale < 0.0 };
+                if !away {
+                    self.clamped_sizer = Some((index, at_min, 0.0));
+                    return false;
+                }
+                let (_, length) = self.axis_start_and_length();
+                let pulled = pulled + scale.abs();
+                if pulled * length < self.hysteresis {
+                    self.clamped_sizer = Some((index, at_min, pulled));
+                    return false;
+                }
+                scale = if at_min { pulled } else { -pulled };
+            }
+            self.clamped_sizer = None;
+        }
+        let (min, max) = self.ratio_range(index);
+        let wanted = self.ratios[index] + scale;
+        let res = wanted.max(min).min(max);
+        if wanted < min {
+            self.clamped_sizer = Some((index, true, 0.0));
+        } else if wanted > max {
+            self.clamped_sizer = Some((index, false, 0.0));
+        }
+        if res == self.ratios[index] {
+            return false;
         }
+        self.ratios[index] = res;
+        self.update_children_sizes();
+        true
+    }
 
-        if res > 0.99 {
-            res = 0.99;
+    /// Returns how far (as ratio) sizer at `index` is from the closest multiple of `snap`. Multiply
+    /// by the length of the split to get pixels. Returns 0 if `snap` isn't positive.
+    pub fn distance_to_nearest_snap(&self, index: usize, snap: f32) -> f32 {
+        if snap <= 0.0 {
+            return 0.0;
         }
+        let ratio = self.ratios[index];
+        (ratio - (ratio / snap).round() * snap).abs()
+    }
 
+    /// Starts inertial movement of sizer at `index` when a touch drag is released. `velocity` is
+    /// in pixels per second, positive towards the end of the split. The first step of `dt`
+    /// seconds is applied right away and `tick_inertia` should be called every frame after that.
+    /// Returns true if the sizer moved.
+    pub fn apply_resize_velocity(&mut self, index: usize, velocity: f32, dt: f32) -> bool {
+        self.inertia = Some((index, velocity));
+        self.tick_inertia(dt)
+    }
+
+    /// Advances inertial resize by `dt` seconds. Velocity decays exponentially and the movement
+    /// stops when it gets below `INERTIA_MIN_VELOCITY` or the sizer hits its limit. Returns true
+    /// if the sizer moved.
+    pub fn tick_inertia(&mut self, dt: f32) -> bool {
+        let (index, velocity) = match self.inertia {
+            Some(inertia) => inertia,
+            None => return false,
+        };
+        let (_, length) = self.axis_start_and_length();
+        let moved = length > 0.0 && self.change_ratio_by_fraction(index, velocity * dt / length);
+        let velocity = velocity * (-INERTIA_DECAY * dt).exp();
+        self.inertia = if moved && velocity.abs() >= INERTIA_MIN_VELOCITY {
+            Some((index, velocity))
+        } else {
+            None
+        };
+        moved
+    }
+
+    /// Returns true while an inertial resize is in progress
+    pub fn has_inertia(&self) -> bool {
+        self.inertia.is_some()
+    }
+
+    /// Moves sizer at `index` by `fraction` of the split. The new ratio is clamped the same way
+    /// as in `change_ratio` but resize hysteresis doesn't apply. Returns false if the ratio
+    /// didn't change.
+    pub fn change_ratio_by_fraction(&mut self, index: usize, fraction: f32) -> bool {
+        self.clamped_sizer = None;
+        let (min, max) = self.ratio_range(index);
+        let res = (self.ratios[index] + fraction).max(min).min(max);
+        if res == self.ratios[index] {
+            return false;
+        }
         self.ratios[index] = res;
         self.update_children_sizes();
+        true
+    }
+
+    /// Moves sizer at `boundary_index` so the children on both sides of it get the same size. If
+    /// minimum sizes don't allow that the sizer is moved as far as they allow.
+    pub fn balance_pair(&mut self, boundary_index: usize) {
+        let previous = match boundary_index {
+            0 => 0.0,
+            _ => self.ratios[boundary_index - 1],
+        };
+        let next = self.ratios[boundary_index + 1];
+        let (min, max) = self.ratio_range(boundary_index);
+        self.ratios[boundary_index] = ((previous + next) * 0.5).max(min).min(max);
+        self.clamped_sizer = None;
+        self.update_children_sizes();
+    }
+
+    /// Gives every child with a preferred size (see `Area::preferred_extent`) that size and
+    /// shares the remaining space between the other children on top of their minimum sizes. If
+    /// there are no such children the last child takes the remainder. When everything doesn't
+    /// fit the preferred sizes are scaled down and the number of pixels that didn't fit is
+    /// returned.
+    pub fn fit_all_to_preferred(&mut self) -> f32 {
+        let (_, length) = self.axis_start_and_length();
+        if length <= 0.0 || self.children.is_empty() {
+            return 0.0;
+        }
+        let direction = self.direction;
+        let mut sizes: Vec<f32> = self.children.iter()
+            .map(|child| child.min_extent(direction))
+            .collect();
+        let preferred: Vec<Option<f32>> = self.children.iter()
+            .map(|child| child.preferred_extent(direction).map(|size| size.max(child.min_extent(direction))))
+            .collect();
+        let flexible_min: f32 = sizes.iter().zip(preferred.iter())
+            .filter(|&(_, p)| p.is_none())
+            .fold(0.0, |sum, (size, _)| sum + size);
+        let preferred_total = preferred.iter().fold(0.0, |sum, p| sum + p.unwrap_or(0.0));
+        let overflow = (preferred_total + flexible_min - length).max(0.0);
+        let scale = if overflow > 0.0 && preferred_total > 0.0 {
+            (length - flexible_min).max(0.0) / preferred_total
+        } else {
+            1.0
+        };
+        for (size, p) in sizes.iter_mut().zip(preferred.iter()) {
+            if let Some(p) = *p {
+                *size = p * scale;
+            }
+        }
+        let remainder = (length - sizes.iter().fold(0.0, |sum, size| sum + size)).max(0.0);
+        let flexible_count = preferred.iter().filter(|p| p.is_none()).count();
+        if flexible_count == 0 {
+            let last = sizes.len() - 1;
+            sizes[last] += remainder;
+        } else {
+            for (size, p) in sizes.iter_mut().zip(preferred.iter()) {
+                if p.is_none() {
+                    *size += remainder / flexible_count as f32;
+                }
+            }
+        }
+        let mut offset = 0.0;
+        for (ratio, size) in self.ratios.iter_mut().zip(sizes.iter()) {
+            offset += *size;
+            *ratio = (offset / length).min(1.0);
+        }
+        let last = self.ratios.len() - 1;
+        self.ratios[last] = 1.0;
+        self.clamped_sizer = None;
+        self.inertia = None;
+        self.update_children_sizes();
+        overflow
     }
 
     pub fn get_dock_handle_at_pos(&self, pos: (f32, f32)) -> Option<DockHandle> {
@@ -105,7 +619,34 @@ impl Split {
         return res;
     }
 
-    pub fn append_child(&mut self, index: usize, child: Area) {
+    /// Returns true if no more children can be added
+    pub fn is_full(&self) -> bool {
+        self.max_children.is_some_and(|max| self.children.len() >= max)
+    }
+
+    /// Checks that this split and all splits below it have child counts within their
+    /// `min_children`/`max_children` limits. Error has handle of the first split that doesn't.
+    pub fn validate(&self) -> ResultView<()> {
+        let count = self.children.len();
+        if self.min_children.is_some_and(|min| count < min) ||
+            self.max_children.is_some_and(|max| count > max) {
+            return Err(Error::InvalidChildCount(self.handle, count));
+        }
+        for child in &self.children {
+            child.validate()?;
+        }
+        Ok(())
+    }
+
+    fn check_not_full(&self) -> ResultView<()> {
+        match self.max_children {
+            Some(max) if self.children.len() >= max => Err(Error::TooManyChildren(max)),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn append_child(&mut self, index: usize, child: Area) -> ResultView<()> {
+        self.check_not_full()?;
         let existing_ratio = self.ratios[index];
         let previous_ratio = match index {
             0 => 0.0,
@@ -114,7 +655,59 @@ impl Split {
         let diff = existing_ratio - previous_ratio;
         self.children.insert(index, child);
         self.ratios.insert(index, existing_ratio - diff / 2.0);
+        self.shift_locked_sizers(index, 1);
+        self.update_children_sizes();
+        Ok(())
+    }
+
+    /// Inserts `child` right after the child at `index`. The existing child keeps `ratio` of its
+    /// space and the new one gets the rest.
+    pub fn insert_child_after(&mut self, index: usize, child: Area, ratio: f32) -> ResultView<()> {
+        self.check_not_full()?;
+        let previous_ratio = match index {
+            0 => 0.0,
+            _ => self.ratios[index - 1]
+        };
+        let old_ratio = self.ratios[index];
+        self.ratios[index] = previous_ratio + (old_ratio - previous_ratio) * ratio;
+        self.ratios.insert(index + 1, old_ratio);
+        self.children.insert(index + 1, child);
+        self.shift_locked_sizers(index, 1);
         self.update_children_sizes();
+        Ok(())
+    }
+
+    /// Inserts dock at `pixel_offset` (measured from the start of the split along its axis). The
+    /// child under the offset is cut there and the new dock takes the part after the offset. The
+    /// cut is kept at least `MIN_RATIO` away from the borders of the child so neither part ends
+    /// up with zero size. Returns index of the new child.
+    pub fn insert_dock_at_pixel(&mut self, dock: Dock, pixel_offset: f32) -> ResultView<usize> {
+        self.check_not_full()?;
+        let (_, length) = self.axis_start_and_length();
+        let ratio = if length > 0.0 {
+            (pixel_offset / length).clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+        let index = self.ratios.iter()
+            .position(|&r| ratio < r)
+            .unwrap_or(self.ratios.len() - 1);
+        let previous_ratio = match index {
+            0 => 0.0,
+            _ => self.ratios[index - 1]
+        };
+        let old_ratio = self.ratios[index];
+        let ratio = if old_ratio - previous_ratio < 2.0 * MIN_RATIO {
+            (previous_ratio + old_ratio) * 0.5
+        } else {
+            ratio.max(previous_ratio + MIN_RATIO).min(old_ratio - MIN_RATIO)
+        };
+        self.ratios[index] = ratio;
+        self.ratios.insert(index + 1, old_ratio);
+        self.children.insert(index + 1, Area::Container(Container::new(dock, Rect::default())));
+        self.shift_locked_sizers(index, 1);
+        self.update_children_sizes();
+        Ok(index + 1)
     }
 
     pub fn remove_child(&mut self, index: usize) {
@@ -122,6 +715,49 @@ impl Split {
         self.ratios.remove(index);
         if index == self.ratios.len() {
             self.ratios[index - 1] = 1.0;
+            self.remove_locked_sizer(index - 1);
+        } else {
+            self.remove_locked_sizer(index);
+        }
+        self.update_children_sizes();
+    }
+
+    /// Replaces child at index with children of a child split. Ratios of the child split are
+    /// scaled into the space the child occupied.
+    pub fn absorb_child_split(&mut self, index: usize) {
+        let no_children = match self.children[index] {
+            Area::Split(ref s) => s.children.is_empty(),
+            _ => false,
+        };
+        // Nothing to absorb, the space goes to a neighbor
+        if no_children {
+            if self.children.len() > 1 {
+                self.remove_child(index);
+            } else {
+                self.children.clear();
+                self.ratios.clear();
+                self.locked_sizers.clear();
+            }
+            return;
+        }
+        let child = match self.children.remove(index) {
+            Area::Split(s) => s,
+            other => {
+                self.children.insert(index, other);
+                return;
+            }
+        };
+        let old_ratio = self.ratios.remove(index);
+        let previous_ratio = match index {
+            0 => 0.0,
+            _ => self.ratios[index - 1]
+        };
+        let diff = old_ratio - previous_ratio;
+        self.shift_locked_sizers(index, child.children.len() - 1);
+        self.locked_sizers.extend(child.locked_sizers.iter().map(|i| index + i));
+        for (child, ratio) in child.children.into_iter().zip(child.ratios.iter()).rev() {
+            self.children.insert(index, child);
+            self.ratios.insert(index, previous_ratio + ratio * diff);
         }
         self.update_children_sizes();
     }
@@ -142,6 +778,7 @@ impl Split {
         for child in children.iter().rev() {
             self.children.insert(index, child.clone());
         }
+        self.shift_locked_sizers(index, children.len() - 1);
 
         let old_ratio = self.ratios.remove(index);
         let previous_ratio = match index {
@@ -161,8 +798,9 @@ impl Split {
 mod test {
     extern crate serde_json;
 
-    use {Split, SplitHandle, Rect, Direction, Area};
+    use {Split, SplitHandle, Rect, Direction, Edge, Area, SplitStyle, Error};
     use super::super::container::Container;
+    use super::MIN_RATIO;
     use dock::{Dock, DockHandle};
 
     #[test]
@@ -198,5 +836,545 @@ mod test {
         assert_eq!(split_out.rect.y as i32, 0);
         assert_eq!(split_out.rect.width as i32, 0);
         assert_eq!(split_out.rect.height as i32, 0);
+        assert!(split_out.style.is_none());
+    }
+
+    #[test]
+    fn test_sizer_range() {
+        let mut first = Container::new(Dock::new(DockHandle(1), "test"), Rect::default());
+        first.min_size = (100.0, 0.0);
+        let mut second = Container::new(Dock::new(DockHandle(2), "test"), Rect::default());
+        second.min_size = (200.0, 0.0);
+        let mut split = Split::from_two(
+            Direction::Vertical,
+            0.6,
+            SplitHandle(1),
+            Rect::new(10.0, 0.0, 1000.0, 100.0),
+            Area::Container(first),
+            Area::Container(Container::new(Dock::new(DockHandle(3), "test"), Rect::default()))
+        );
+        split.append_child(1, Area::Container(second)).unwrap();
+        assert_eq!(split.ratios, vec![0.6, 0.8, 1.0]);
+
+        assert_eq!(split.sizer_range(0), (110.0, 610.0));
+        assert_eq!(split.sizer_range(1), (810.0, 1000.0));
+
+        split.change_ratio(0, (1000.0, 0.0));
+        assert_eq!(split.ratios[0], 0.1);
+        split.change_ratio(0, (-1000.0, 0.0));
+        assert_eq!(split.ratios[0], 0.6);
+    }
+
+    #[test]
+    fn test_max_children() {
+        let leaf = |i| Area::Container(Container::new(Dock::new(DockHandle(i), "test"), Rect::default()));
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 300.0, 100.0), vec![leaf(1), leaf(2)]);
+        split.max_children = Some(3);
+
+        split.append_child(1, leaf(3)).unwrap();
+        assert!(split.is_full());
+        let ratios = split.ratios.clone();
+
+        match split.append_child(1, leaf(4)) {
+            Err(Error::TooManyChildren(3)) => {},
+            other => panic!("expected TooManyChildren, got {:?}", other),
+        }
+        assert!(split.insert_child_after(0, leaf(5), 0.5).is_err());
+        assert!(split.insert_dock_at_pixel(Dock::new(DockHandle(6), "test"), 10.0).is_err());
+        assert_eq!(split.children.len(), 3);
+        assert_eq!(split.ratios, ratios);
+
+        let serialized = serde_json::to_string(&split).unwrap();
+        let split_out: Split = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(split_out.max_children, Some(3));
+    }
+
+    #[test]
+    fn test_min_children_validate() {
+        let leaf = |i| Area::Container(Container::new(Dock::new(DockHandle(i), "test"), Rect::default()));
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 300.0, 100.0), vec![leaf(1), leaf(2)]);
+        let inner = Split::from_children(Direction::Horizontal, SplitHandle(2), Rect::default(), vec![leaf(3), leaf(4)]);
+        split.replace_child(1, Area::Split(inner));
+        split.min_children = Some(2);
+        split.validate().unwrap();
+
+        let serialized = serde_json::to_string(&split).unwrap();
+        let split_out: Split = serde_json::from_str(&serialized.replace(r#""min_children":null"#, r#""min_children":2"#)).unwrap();
+        assert_eq!(split_out.min_children, Some(2));
+        split_out.validate().unwrap();
+
+        // Edited by hand: inner split has two children but requires three
+        let split_out: Split = serde_json::from_str(&serialized.replace(r#""min_children":null"#, r#""min_children":3"#)).unwrap();
+        match split_out.validate() {
+            Err(Error::InvalidChildCount(SplitHandle(2), 2)) => {},
+            other => panic!("expected InvalidChildCount, got {:?}", other),
+        }
+
+        // Outer split allows only one child
+        let split_out: Split = serde_json::from_str(&serialized.replace(r#""max_children":null"#, r#""max_children":1"#)).unwrap();
+        match split_out.validate() {
+            Err(Error::InvalidChildCount(SplitHandle(1), 2)) => {},
+            other => panic!("expected InvalidChildCount, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_insert_dock_at_pixel() {
+        let mut split = Split::from_children(
+            Direction::Vertical,
+            SplitHandle(1),
+            Rect::new(50.0, 0.0, 300.0, 100.0),
+            (0..3).map(|i| Area::Container(Container::new(Dock::new(DockHandle(i), "test"), Rect::default()))).collect()
+        );
+
+        let index = split.insert_dock_at_pixel(Dock::new(DockHandle(7), "new"), 150.0).unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(split.children.len(), 4);
+        assert_eq!(split.children[1].get_rect(), Rect::new(150.0, 0.0, 50.0, 100.0));
+        assert_eq!(split.children[2].get_rect(), Rect::new(200.0, 0.0, 50.0, 100.0));
+        assert!(split.children[2].find_container_by_dock_handle(DockHandle(7)).is_some());
+        assert_eq!(split.children[3].get_rect(), Rect::new(250.0, 0.0, 100.0, 100.0));
+    }
+
+    #[test]
+    fn test_insert_dock_at_pixel_edges() {
+        let make = || Split::from_children(
+            Direction::Vertical,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 300.0, 100.0),
+            (0..3).map(|i| Area::Container(Container::new(Dock::new(DockHandle(i), "test"), Rect::default()))).collect()
+        );
+        let widths = |split: &Split| -> Vec<i32> {
+            split.children.iter().map(|child| child.get_rect().width.round() as i32).collect()
+        };
+
+        let mut split = make();
+        assert_eq!(split.insert_dock_at_pixel(Dock::new(DockHandle(7), "new"), 0.0).unwrap(), 1);
+        assert_eq!(widths(&split), vec![3, 97, 100, 100]);
+
+        let mut split = make();
+        assert_eq!(split.insert_dock_at_pixel(Dock::new(DockHandle(7), "new"), 300.0).unwrap(), 3);
+        assert_eq!(widths(&split), vec![100, 100, 97, 3]);
+
+        // On the border between the first and second child the second one is cut
+        let mut split = make();
+        assert_eq!(split.insert_dock_at_pixel(Dock::new(DockHandle(7), "new"), 100.0).unwrap(), 2);
+        assert_eq!(widths(&split), vec![100, 3, 97, 100]);
+    }
+
+    #[test]
+    fn test_overflow_scroll() {
+        let children = (0..3).map(|i| {
+            let mut c = Container::new(Dock::new(DockHandle(i), "test"), Rect::default());
+            c.min_size = (200.0, 0.0);
+            Area::Container(c)
+        }).collect();
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(10.0, 0.0, 400.0, 100.0), children);
+
+        assert_eq!(split.content_extent(), 600.0);
+        assert_eq!(split.children[1].get_rect(), Rect::new(210.0, 0.0, 200.0, 100.0));
+
+        split.set_scroll_offset(150.0);
+        assert_eq!(split.children[0].get_rect(), Rect::new(-140.0, 0.0, 200.0, 100.0));
+        assert_eq!(split.children[2].get_rect(), Rect::new(260.0, 0.0, 200.0, 100.0));
+
+        split.set_scroll_offset(1000.0);
+        assert_eq!(split.scroll_offset, 200.0);
+
+        split.update_rect(Rect::new(10.0, 0.0, 900.0, 100.0));
+        assert_eq!(split.content_extent(), 900.0);
+        assert_eq!(split.scroll_offset, 0.0);
+        assert_eq!(split.children[1].get_rect(), Rect::new(310.0, 0.0, 300.0, 100.0));
+    }
+
+    #[test]
+    fn test_set_direction_remap() {
+        let children = (0..3).map(|i| {
+            let mut c = Container::new(Dock::new(DockHandle(i), "test"), Rect::default());
+            c.min_size = (200.0, 0.0);
+            Area::Container(c)
+        }).collect();
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 400.0, 300.0), children);
+        split.ratios = vec![0.1, 0.2, 1.0];
+        split.update_rect(Rect::new(0.0, 0.0, 400.0, 300.0));
+        // Children overflow so they are all 200 wide regardless of the ratios
+        assert_eq!(split.children[0].get_rect().width, 200.0);
+
+        split.set_direction(Direction::Horizontal, true);
+        assert_eq!(split.direction, Direction::Horizontal);
+        for child in &split.children {
+            assert!((child.get_rect().height - 100.0).abs() < 0.001);
+            assert_eq!(child.get_rect().width, 400.0);
+        }
+
+        let mut split = Split::from_two(
+            Direction::Vertical,
+            0.25,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 400.0, 200.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default())));
+        split.set_direction(Direction::Horizontal, true);
+        assert_eq!(split.children[0].get_rect().height, 50.0);
+        assert_eq!(split.children[1].get_rect().height, 150.0);
+
+        split.set_direction(Direction::Vertical, false);
+        assert_eq!(split.ratios, vec![0.25, 1.0]);
+        assert_eq!(split.children[0].get_rect().width, 100.0);
+    }
+
+    #[test]
+    fn test_repair_ratios() {
+        let leaf = |i| Area::Container(Container::new(Dock::new(DockHandle(i), "test"), Rect::default()));
+        let children = (0..5).map(leaf).collect();
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0), children);
+        split.ratios = vec![0.7, -0.2, 0.3, 0.3, 0.9];
+
+        split.repair_ratios();
+        assert_eq!(split.ratios.len(), 5);
+        assert!(split.ratios[0] > 0.0);
+        for pair in split.ratios.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+        assert_eq!(split.ratios[4], 1.0);
+        assert!((split.ratios[1] - 0.3).abs() < 0.0001);
+        assert!(split.ratios[2] > 0.3 && split.ratios[2] < 0.7);
+        for child in &split.children {
+            assert!(child.get_rect().width > 0.0);
+        }
+
+        split.ratios = vec![1.0, 1.0, 1.0, 1.0, 1.0];
+        split.repair_ratios();
+        for pair in split.ratios.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+        assert_eq!(split.ratios[4], 1.0);
+    }
+
+    #[test]
+    fn test_resize_hysteresis() {
+        let mut split = Split::from_two(
+            Direction::Vertical,
+            0.5,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default()))
+        );
+        let mut second = Container::new(Dock::new(DockHandle(2), "test"), Rect::default());
+        second.min_size = (40.0, 0.0);
+        split.children[1] = Area::Container(second);
+
+        // Push the sizer against the minimum size of the second child
+        split.change_ratio(0, (-30.0, 0.0));
+        assert_eq!(split.ratios[0], 0.6);
+        for _ in 0..10 {
+            assert!(!split.change_ratio(0, (1.0, 0.0)));
+            assert!(!split.change_ratio(0, (-1.0, 0.0)));
+            assert_eq!(split.ratios[0], 0.6);
+        }
+
+        // Dragging back further than the hysteresis releases it
+        assert!(!split.change_ratio(0, (1.0, 0.0)));
+        assert!(split.change_ratio(0, (1.5, 0.0)));
+        assert!((split.ratios[0] - 0.575).abs() < 0.0001);
+
+        split.hysteresis = 0.0;
+        split.change_ratio(0, (-30.0, 0.0));
+        assert!(split.change_ratio(0, (1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_resize_hysteresis_limit_moved() {
+        let mut split = Split::from_two(
+            Direction::Vertical,
+            0.5,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default()))
+        );
+        if let Area::Container(ref mut c) = split.children[1] {
+            c.min_size = (40.0, 0.0);
+        }
+        split.change_ratio(0, (-30.0, 0.0));
+        assert_eq!(split.ratios[0], 0.6);
+
+        // Growing the split gives the sizer room towards the old limit
+        split.update_rect(Rect::new(0.0, 0.0, 400.0, 100.0));
+        assert!(split.change_ratio(0, (-30.0, 0.0)));
+        assert!((split.ratios[0] - 0.675).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_fixed_children() {
+        let leaf = |i, fixed| {
+            let mut c = Container::new(Dock::new(DockHandle(i), "test"), Rect::default());
+            c.fixed = fixed;
+            Area::Container(c)
+        };
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 400.0, 100.0),
+                                             vec![leaf(1, false), leaf(2, true), leaf(3, false)]);
+        split.ratios = vec![0.25, 0.5, 1.0];
+        split.update_children_sizes();
+
+        split.update_rect(Rect::new(0.0, 0.0, 700.0, 100.0));
+        let widths: Vec<f32> = split.children.iter().map(|child| child.get_rect().width).collect();
+        assert!((widths[1] - 100.0).abs() < 0.001);
+        assert!((widths[0] - 200.0).abs() < 0.001);
+        assert!((widths[2] - 400.0).abs() < 0.001);
+
+        // Shrinking below the fixed size takes everything from the flexible ones first
+        split.update_rect(Rect::new(0.0, 0.0, 80.0, 100.0));
+        let widths: Vec<f32> = split.children.iter().map(|child| child.get_rect().width).collect();
+        assert!(widths[0].abs() < 0.001 && widths[2].abs() < 0.001);
+        assert!((widths[1] - 80.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_sizer_at_limit() {
+        let leaf = |i, min_height| {
+            let mut c = Container::new(Dock::new(DockHandle(i), "test"), Rect::default());
+            c.min_size = (0.0, min_height);
+            Area::Container(c)
+        };
+        let mut split = Split::from_children(Direction::Horizontal, SplitHandle(1), Rect::new(0.0, 0.0, 100.0, 100.0),
+                                             vec![leaf(1, 0.0), leaf(2, 30.0), leaf(3, 0.0)]);
+        assert_eq!(split.sizer_at_limit(0), None);
+
+        // Second child shrinks to its minimum from above
+        split.change_ratio(0, (0.0, -100.0));
+        assert!((split.children[1].get_rect().height - 30.0).abs() < 0.001);
+        assert_eq!(split.sizer_at_limit(0), Some(Edge::Bottom));
+        // The sizer below it can't move up either
+        assert_eq!(split.sizer_at_limit(1), Some(Edge::Top));
+
+        split.change_ratio(1, (0.0, -5.0));
+        assert_eq!(split.sizer_at_limit(1), None);
+        split.change_ratio(1, (0.0, -100.0));
+        assert_eq!(split.ratios[1], 0.99);
+        assert_eq!(split.sizer_at_limit(1), Some(Edge::Bottom));
+    }
+
+    #[test]
+    fn test_resize_preserving() {
+        let leaf = |i| Area::Container(Container::new(Dock::new(DockHandle(i), "test"), Rect::default()));
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 300.0, 100.0), vec![leaf(1), leaf(2), leaf(3)]);
+
+        split.resize_preserving(Rect::new(0.0, 0.0, 500.0, 100.0), 1);
+        assert!((split.children[1].get_rect().width - 100.0).abs() < 0.001);
+        assert!((split.children[0].get_rect().width - 200.0).abs() < 0.001);
+        assert!((split.children[2].get_rect().width - 200.0).abs() < 0.001);
+        assert_eq!(split.ratios[2], 1.0);
+
+        split.resize_preserving(Rect::new(0.0, 0.0, 200.0, 100.0), 1);
+        assert!((split.children[1].get_rect().width - 100.0).abs() < 0.001);
+        assert!((split.children[0].get_rect().width - 50.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_change_ratio_by_fraction() {
+        let mut split = Split::from_two(
+            Direction::Horizontal,
+            0.5,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 200.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default()))
+        );
+
+        assert!(split.change_ratio_by_fraction(0, 0.05));
+        assert_eq!(split.ratios[0], 0.5 + 0.05);
+        assert_eq!(split.children[0].get_rect().height, 110.0);
+
+        assert!(split.change_ratio_by_fraction(0, -2.0));
+        assert_eq!(split.ratios[0], 0.01);
+        assert!(!split.change_ratio_by_fraction(0, -0.05));
+    }
+
+    #[test]
+    fn test_distance_to_nearest_snap() {
+        let split = Split::from_two(
+            Direction::Vertical,
+            0.22,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default()))
+        );
+        assert!((split.distance_to_nearest_snap(0, 0.25) - 0.03).abs() < 0.0001);
+        assert!((split.distance_to_nearest_snap(0, 0.1) - 0.02).abs() < 0.0001);
+        assert_eq!(split.distance_to_nearest_snap(1, 0.25), 0.0);
+        assert_eq!(split.distance_to_nearest_snap(0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_resize_inertia() {
+        let mut split = Split::from_two(
+            Direction::Vertical,
+            0.5,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 1000.0, 100.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default()))
+        );
+        let dt = 1.0 / 60.0;
+
+        assert!(split.apply_resize_velocity(0, 200.0, dt));
+        let mut previous = split.ratios[0];
+        assert!(previous > 0.5);
+        let mut frames = 0;
+        while split.tick_inertia(dt) {
+            assert!(split.ratios[0] > previous);
+            previous = split.ratios[0];
+            frames += 1;
+            assert!(frames < 1000);
+        }
+        assert!(frames > 10);
+        assert!(!split.has_inertia());
+        // Total distance of exponential decay is velocity / decay = 50px
+        assert!(split.ratios[0] < 0.55);
+
+        // Fast fling stops at the limit
+        assert!(split.apply_resize_velocity(0, -100000.0, dt));
+        while split.tick_inertia(dt) {}
+        assert_eq!(split.ratios[0], MIN_RATIO);
+
+        // New drag cancels the movement
+        split.apply_resize_velocity(0, 500.0, dt);
+        split.change_ratio(0, (-1.0, 0.0));
+        assert!(!split.tick_inertia(dt));
+    }
+
+    #[test]
+    fn test_balance_pair() {
+        let mut split = Split::from_two(
+            Direction::Vertical,
+            0.2,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default()))
+        );
+        split.append_child(1, Area::Container(Container::new(Dock::new(DockHandle(3), "test"), Rect::default()))).unwrap();
+        split.update_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+
+        split.balance_pair(1);
+        let width_1 = split.children[1].get_rect().width;
+        let width_2 = split.children[2].get_rect().width;
+        assert!((width_1 - width_2).abs() < 0.001);
+        assert!((width_1 - 40.0).abs() < 0.001);
+
+        if let Area::Container(ref mut c) = split.children[0] {
+            c.min_size = (40.0, 0.0);
+        }
+        split.balance_pair(0);
+        // 30/30 would be balanced but the first child can't get narrower than 40
+        assert!((split.children[0].get_rect().width - 40.0).abs() < 0.001);
+        assert!((split.children[1].get_rect().width - 20.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_locked_sizers_follow_children() {
+        let leaf = |i| Area::Container(Container::new(Dock::new(DockHandle(i), "test"), Rect::default()));
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 400.0, 100.0),
+                                             vec![leaf(1), leaf(2), leaf(3)]);
+        // Sizer between docks 2 and 3
+        split.set_sizer_locked(1, true);
+
+        split.append_child(0, leaf(4)).unwrap();
+        assert!(!split.is_sizer_locked(1));
+        assert!(split.is_sizer_locked(2));
+        assert_eq!(split.sizers(8.0).len(), 2);
+
+        split.insert_child_after(3, leaf(5), 0.5).unwrap();
+        assert!(split.is_sizer_locked(2));
+        split.insert_dock_at_pixel(Dock::new(DockHandle(6), "test"), 10.0).unwrap();
+        assert!(split.is_sizer_locked(3));
+
+        split.remove_child(0);
+        assert!(split.is_sizer_locked(2));
+        // Removing dock 2 removes the locked sizer too
+        split.remove_child(2);
+        assert!(split.locked_sizers.is_empty());
+
+        split.set_sizer_locked(0, true);
+        split.reverse_children();
+        assert!(split.is_sizer_locked(split.ratios.len() - 2));
+    }
+
+    #[test]
+    fn test_fit_all_to_preferred() {
+        let children = (1..4).map(|i| {
+            let mut c = Container::new(Dock::new(DockHandle(i), "test"), Rect::default());
+            c.preferred_size = match i {
+                1 => Some((100.0, 0.0)),
+                3 => Some((50.0, 0.0)),
+                _ => None,
+            };
+            Area::Container(c)
+        }).collect();
+        let mut split = Split::from_children(Direction::Vertical, SplitHandle(1), Rect::new(0.0, 0.0, 400.0, 100.0), children);
+
+        assert_eq!(split.fit_all_to_preferred(), 0.0);
+        assert!((split.children[0].get_rect().width - 100.0).abs() < 0.001);
+        assert!((split.children[1].get_rect().width - 250.0).abs() < 0.001);
+        assert!((split.children[2].get_rect().width - 50.0).abs() < 0.001);
+        assert_eq!(split.ratios[2], 1.0);
+
+        if let Area::Container(ref mut c) = split.children[0] {
+            c.preferred_size = Some((300.0, 0.0));
+        }
+        if let Area::Container(ref mut c) = split.children[1] {
+            c.min_size = (40.0, 0.0);
+        }
+        // 300 + 40 + 50 doesn't fit in 300 so the preferred sizes share what is left
+        split.update_rect(Rect::new(0.0, 0.0, 300.0, 100.0));
+        assert!((split.fit_all_to_preferred() - 90.0).abs() < 0.001);
+        assert!((split.children[0].get_rect().width - 222.857).abs() < 0.01);
+        assert!((split.children[1].get_rect().width - 40.0).abs() < 0.001);
+        assert!((split.children[2].get_rect().width - 37.143).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_change_ratio_no_op() {
+        let mut split = Split::from_two(
+            Direction::Vertical,
+            0.5,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Area::Container(Container::new(Dock::new(DockHandle(1), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(2), "test"), Rect::default()))
+        );
+        // children rects are only touched when sizes are recalculated
+        split.children[0].update_rect(Rect::default());
+
+        assert!(!split.change_ratio(0, (0.0, 25.0)));
+        assert_eq!(split.ratios[0], 0.5);
+        assert_eq!(split.children[0].get_rect(), Rect::default());
+
+        assert!(split.change_ratio(0, (1000.0, 0.0)));
+        assert!(!split.change_ratio(0, (10.0, 0.0)));
+        assert_eq!(split.ratios[0], 0.01);
+        assert_eq!(split.children[0].get_rect().width, 1.0);
+    }
+
+    #[test]
+    fn test_split_style_serialize() {
+        let mut split_in = Split::from_two(
+            Direction::Vertical,
+            0.3,
+            SplitHandle(1),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Area::Container(Container::new(Dock::new(DockHandle(14), "test"), Rect::default())),
+            Area::Container(Container::new(Dock::new(DockHandle(15), "test2"), Rect::default()))
+        );
+        split_in.style = Some(SplitStyle::new(0xff0000ff, 2.0));
+
+        let serialized = serde_json::to_string(&split_in).unwrap();
+        let split_out: Split = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(split_out.style, Some(SplitStyle::new(0xff0000ff, 2.0)));
+        assert_eq!(split_out.ratios, vec![0.3, 1.0]);
     }
 }
\ No newline at end of file
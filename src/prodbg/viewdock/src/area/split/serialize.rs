@@ -1,5 +1,5 @@
 extern crate serde;
-use super::{Split, SplitHandle};
+use super::{Split, SplitHandle, RESIZE_HYSTERESIS};
 use rect::Rect;
 
 gen_handle!("SplitHandle", SplitHandle, SplitHandleVisitor);
@@ -18,10 +18,14 @@ struct SplitMapVisitor<'a> {
 
 impl<'a> serde::ser::MapVisitor for SplitMapVisitor<'a> {
     fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
-        try!(serializer.serialize_struct_elt("children", &self.value.children));
-        try!(serializer.serialize_struct_elt("ratios", &self.value.ratios));
-        try!(serializer.serialize_struct_elt("direction", &self.value.direction));
-        try!(serializer.serialize_struct_elt("handle", &self.value.handle));
+        serializer.serialize_struct_elt("children", &self.value.children)?;
+        serializer.serialize_struct_elt("ratios", &self.value.ratios)?;
+        serializer.serialize_struct_elt("direction", &self.value.direction)?;
+        serializer.serialize_struct_elt("handle", &self.value.handle)?;
+        serializer.serialize_struct_elt("style", self.value.style)?;
+        serializer.serialize_struct_elt("min_children", self.value.min_children)?;
+        serializer.serialize_struct_elt("max_children", self.value.max_children)?;
+        serializer.serialize_struct_elt("hysteresis", self.value.hysteresis)?;
         Ok(None)
     }
 }
@@ -30,7 +34,7 @@ impl<'a> serde::ser::MapVisitor for SplitMapVisitor<'a> {
 
 impl serde::Deserialize for Split {
     fn deserialize<D>(deserializer: &mut D) -> Result<Split, D::Error> where D: serde::de::Deserializer {
-        static FIELDS: &'static [&'static str] = &[ "left", "right", "left_docks", "right_docks", "ratio", "direction", "handle"];
+        static FIELDS: &[&str] = &[ "left", "right", "left_docks", "right_docks", "ratio", "direction", "handle", "style", "min_children", "max_children", "hysteresis"];
         deserializer.deserialize_struct("Split", FIELDS, SplitVisitor)
     }
 }
@@ -45,45 +49,61 @@ impl serde::de::Visitor for SplitVisitor {
         let mut ratios = None;
         let mut direction = None;
         let mut handle = None;
+        let mut style = None;
+        let mut min_children = None;
+        let mut max_children = None;
+        let mut hysteresis = None;
 
         loop {
-            match try!(visitor.visit_key()) {
-                Some(SplitField::Children) => { children = Some(try!(visitor.visit_value())); }
-                Some(SplitField::Ratios) => { ratios = Some(try!(visitor.visit_value())); }
-                Some(SplitField::Direction) => { direction = Some(try!(visitor.visit_value())); }
-                Some(SplitField::Handle) => { handle = Some(try!(visitor.visit_value())); }
+            match visitor.visit_key()? {
+                Some(SplitField::Children) => { children = Some(visitor.visit_value()?); }
+                Some(SplitField::Ratios) => { ratios = Some(visitor.visit_value()?); }
+                Some(SplitField::Direction) => { direction = Some(visitor.visit_value()?); }
+                Some(SplitField::Handle) => { handle = Some(visitor.visit_value()?); }
+                Some(SplitField::Style) => { style = visitor.visit_value()?; }
+                Some(SplitField::MinChildren) => { min_children = visitor.visit_value()?; }
+                Some(SplitField::MaxChildren) => { max_children = visitor.visit_value()?; }
+                Some(SplitField::Hysteresis) => { hysteresis = Some(visitor.visit_value()?); }
                 None => { break; }
             }
         }
 
         let children = match children {
             Some(right_docks) => right_docks,
-            None => try!(visitor.missing_field("right_docks")),
+            None => visitor.missing_field("right_docks")?,
         };
 
         let ratios = match ratios {
             Some(ratio) => ratio,
-            None => try!(visitor.missing_field("ratios")),
+            None => visitor.missing_field("ratios")?,
         };
 
         let direction = match direction {
             Some(direction) => direction,
-            None => try!(visitor.missing_field("direction")),
+            None => visitor.missing_field("direction")?,
         };
 
         let handle = match handle {
             Some(handle) => handle,
-            None => try!(visitor.missing_field("handle")),
+            None => visitor.missing_field("handle")?,
         };
 
-        try!(visitor.end());
+        visitor.end()?;
 
         Ok(Split {
-            children: children,
-            ratios: ratios,
-            direction: direction,
-            handle: handle,
+            children,
+            ratios,
+            direction,
+            handle,
             rect: Rect::default(), // reconstructed during update
+            style,
+            scroll_offset: 0.0,
+            min_children,
+            max_children,
+            hysteresis: hysteresis.unwrap_or(RESIZE_HYSTERESIS),
+            clamped_sizer: None,
+            inertia: None,
+            locked_sizers: Vec::new(),
         })
     }
 }
@@ -93,6 +113,10 @@ enum SplitField {
     Ratios,
     Direction,
     Handle,
+    Style,
+    MinChildren,
+    MaxChildren,
+    Hysteresis,
 }
 
 impl serde::Deserialize for SplitField {
@@ -109,7 +133,11 @@ impl serde::Deserialize for SplitField {
                         "ratios" => Ok(SplitField::Ratios),
                         "direction" => Ok(SplitField::Direction),
                         "handle" => Ok(SplitField::Handle),
-                        _ => Err(serde::de::Error::custom("expected left, right, left_docks, right_docs, ratio, direction or handle")),
+                        "style" => Ok(SplitField::Style),
+                        "min_children" => Ok(SplitField::MinChildren),
+                        "max_children" => Ok(SplitField::MaxChildren),
+                        "hysteresis" => Ok(SplitField::Hysteresis),
+                        _ => Err(serde::de::Error::custom("expected left, right, left_docks, right_docs, ratio, direction, handle, style, min_children, max_children or hysteresis")),
                     }
                 }
         }
@@ -0,0 +1,35 @@
+mod serialize;
+
+/// Rendering hints for a split or container. Layout code only carries this around, it's up to
+/// the renderer to use it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SplitStyle {
+    /// Background color in 0xRRGGBBAA format
+    pub background: u32,
+    /// Padding around content (in pixels)
+    pub padding: f32,
+}
+
+impl SplitStyle {
+    pub fn new(background: u32, padding: f32) -> SplitStyle {
+        SplitStyle {
+            background,
+            padding,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate serde_json;
+    use SplitStyle;
+
+    #[test]
+    fn test_style_serialize() {
+        let style_in = SplitStyle::new(0x20304050, 3.0);
+        let serialized = serde_json::to_string(&style_in).unwrap();
+        let style_out: SplitStyle = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(style_in, style_out);
+    }
+}
@@ -0,0 +1,93 @@
+extern crate serde;
+use super::SplitStyle;
+
+// Serialization
+
+impl serde::ser::Serialize for SplitStyle {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: serde::ser::Serializer {
+        serializer.serialize_struct("SplitStyle", SplitStyleMapVisitor { value: self }).map(|_| ())
+    }
+}
+
+struct SplitStyleMapVisitor<'a> {
+    value: &'a SplitStyle
+}
+
+impl<'a> serde::ser::MapVisitor for SplitStyleMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
+        serializer.serialize_struct_elt("background", self.value.background)?;
+        serializer.serialize_struct_elt("padding", self.value.padding)?;
+        Ok(None)
+    }
+}
+
+// Deserialization
+
+impl serde::Deserialize for SplitStyle {
+    fn deserialize<D>(deserializer: &mut D) -> Result<SplitStyle, D::Error> where D: serde::de::Deserializer {
+        static FIELDS: &[&str] = &["background", "padding"];
+        deserializer.deserialize_struct("SplitStyle", FIELDS, SplitStyleVisitor)
+    }
+}
+
+struct SplitStyleVisitor;
+
+impl serde::de::Visitor for SplitStyleVisitor {
+    type Value = SplitStyle;
+
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<SplitStyle, V::Error> where V: serde::de::MapVisitor {
+        let mut background = None;
+        let mut padding = None;
+
+        loop {
+            match visitor.visit_key()? {
+                Some(SplitStyleField::Background) => { background = Some(visitor.visit_value()?); }
+                Some(SplitStyleField::Padding) => { padding = Some(visitor.visit_value()?); }
+                None => { break; }
+            }
+        }
+
+        let background = match background {
+            Some(background) => background,
+            None => visitor.missing_field("background")?,
+        };
+
+        let padding = match padding {
+            Some(padding) => padding,
+            None => visitor.missing_field("padding")?,
+        };
+
+        visitor.end()?;
+
+        Ok(SplitStyle {
+            background,
+            padding,
+        })
+    }
+}
+
+enum SplitStyleField {
+    Background,
+    Padding,
+}
+
+impl serde::Deserialize for SplitStyleField {
+    fn deserialize<D>(deserializer: &mut D) -> Result<SplitStyleField, D::Error> where D: serde::de::Deserializer {
+        struct SplitStyleFieldVisitor;
+
+        impl serde::de::Visitor for SplitStyleFieldVisitor {
+            type Value = SplitStyleField;
+
+            fn visit_str<E>(&mut self, value: &str) -> Result<SplitStyleField, E>
+                where E: serde::de::Error {
+                    match value {
+                        "background" => Ok(SplitStyleField::Background),
+                        "padding" => Ok(SplitStyleField::Padding),
+                        _ => Err(serde::de::Error::custom("expected background or padding")),
+                    }
+                }
+        }
+
+        deserializer.deserialize(SplitStyleFieldVisitor)
+    }
+}
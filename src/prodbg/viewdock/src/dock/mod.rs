@@ -3,7 +3,7 @@ mod serialize;
 use rect::Rect;
 
 /// Handle to a dock
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DockHandle(pub u64);
 
 /// Holds information about the plugin view, data and handle
@@ -12,7 +12,14 @@ pub struct Dock {
     pub handle: DockHandle,
     pub plugin_name: String,
     pub plugin_data: Option<Vec<String>>,
-    pub rect: Rect
+    pub rect: Rect,
+    /// Pinned tabs are kept first in their container and can't be closed
+    pub pinned: bool,
+    /// Set on docks that were tabs of the same container before it was exploded into a split so
+    /// they can be grouped again with `Area::regroup`
+    pub tab_group_id: Option<u64>,
+    /// Scale the renderer should draw the content of the dock at. Doesn't affect the layout
+    pub content_scale: f32,
 }
 
 impl Dock {
@@ -22,14 +29,50 @@ impl Dock {
             plugin_name: plugin_name.to_owned(),
             plugin_data: None,
             rect: Rect::default(),
+            pinned: false,
+            tab_group_id: None,
+            content_scale: 1.0,
+        }
+    }
+
+    /// Sets scale of the content. Values that aren't positive are ignored
+    pub fn set_content_scale(&mut self, scale: f32) {
+        if scale > 0.0 {
+            self.content_scale = scale;
         }
     }
 }
 
+/// Geometry free description of a dock. A list of these is enough to recreate the open views
+/// in some other layout.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DockManifestEntry {
+    pub handle: DockHandle,
+    pub plugin_name: String,
+    pub plugin_data: Option<Vec<String>>,
+}
+
+impl DockManifestEntry {
+    pub fn from_dock(dock: &Dock) -> DockManifestEntry {
+        DockManifestEntry {
+            handle: dock.handle,
+            plugin_name: dock.plugin_name.clone(),
+            plugin_data: dock.plugin_data.clone(),
+        }
+    }
+
+    /// Creates a new dock for this entry. Placement of the dock is up to the caller.
+    pub fn to_dock(&self) -> Dock {
+        let mut dock = Dock::new(self.handle, &self.plugin_name);
+        dock.plugin_data = self.plugin_data.clone();
+        dock
+    }
+}
+
 #[cfg(test)]
 mod test {
     extern crate serde_json;
-    use {Dock, DockHandle, Rect};
+    use {Dock, DockHandle, DockManifestEntry, Rect};
 
     #[test]
     fn test_dockhandle_serialize() {
@@ -46,7 +89,10 @@ mod test {
             handle: DockHandle(1),
             plugin_name: "disassembly".to_owned(),
             plugin_data: None,
-            rect: Rect::new(1.0, 2.0, 3.0, 4.0)
+            rect: Rect::new(1.0, 2.0, 3.0, 4.0),
+            pinned: false,
+            tab_group_id: None,
+            content_scale: 1.0,
         };
 
         let serialized = serde_json::to_string(&dock_in).unwrap();
@@ -68,7 +114,10 @@ mod test {
             handle: DockHandle(1),
             plugin_name: "registers".to_owned(),
             plugin_data: Some(vec!["some_data".to_owned(), "more_data".to_owned()]),
-            rect: Rect::new(4.0, 5.0, 2.0, 8.0)
+            rect: Rect::new(4.0, 5.0, 2.0, 8.0),
+            pinned: true,
+            tab_group_id: Some(7),
+            content_scale: 1.0,
         };
 
         let serialized = serde_json::to_string(&dock_in).unwrap();
@@ -77,6 +126,8 @@ mod test {
         assert_eq!(dock_in.handle, dock_out.handle);
         assert_eq!(dock_in.plugin_name, dock_out.plugin_name);
         assert_eq!(dock_in.plugin_data, dock_out.plugin_data);
+        assert!(dock_out.pinned);
+        assert_eq!(dock_out.tab_group_id, Some(7));
 
         // expect that rect is not serialized and set to zero
         assert_eq!(dock_out.rect.x as i32, 0);
@@ -91,4 +142,34 @@ mod test {
         assert_eq!(plugin_data[1], "more_data");
     }
 
-}
\ No newline at end of file
+
+    #[test]
+    fn test_manifest_entry_serialize() {
+        let mut dock = Dock::new(DockHandle(3), "registers");
+        dock.plugin_data = Some(vec!["eax".to_owned()]);
+        let entry_in = DockManifestEntry::from_dock(&dock);
+        let serialized = serde_json::to_string(&entry_in).unwrap();
+        let entry_out: DockManifestEntry = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(entry_in, entry_out);
+        assert_eq!(entry_out.to_dock().plugin_data, dock.plugin_data);
+    }
+
+    #[test]
+    fn test_content_scale_serialize() {
+        let mut dock_in = Dock::new(DockHandle(2), "minimap");
+        dock_in.rect = Rect::new(10.0, 20.0, 30.0, 40.0);
+        dock_in.set_content_scale(0.5);
+        dock_in.set_content_scale(0.0);
+        assert_eq!(dock_in.content_scale, 0.5);
+        assert_eq!(dock_in.rect, Rect::new(10.0, 20.0, 30.0, 40.0));
+
+        let serialized = serde_json::to_string(&dock_in).unwrap();
+        let dock_out: Dock = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(dock_out.content_scale, 0.5);
+        assert_eq!(dock_out.rect, Rect::default());
+
+        let dock_old: Dock = serde_json::from_str(r#"{"handle":1,"plugin_name":"a","plugin_data":null}"#).unwrap();
+        assert_eq!(dock_old.content_scale, 1.0);
+    }
+}
@@ -1,5 +1,5 @@
 extern crate serde;
-use super::{Dock, DockHandle};
+use super::{Dock, DockHandle, DockManifestEntry};
 use rect::Rect;
 
 gen_handle!("DockHandle", DockHandle, DockHandleVisitor);
@@ -18,9 +18,12 @@ struct DockMapVisitor<'a> {
 
 impl<'a> serde::ser::MapVisitor for DockMapVisitor<'a> {
     fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
-        try!(serializer.serialize_struct_elt("handle", &self.value.handle));
-        try!(serializer.serialize_struct_elt("plugin_name", &self.value.plugin_name));
-        try!(serializer.serialize_struct_elt("plugin_data", &self.value.plugin_data));
+        serializer.serialize_struct_elt("handle", &self.value.handle)?;
+        serializer.serialize_struct_elt("plugin_name", &self.value.plugin_name)?;
+        serializer.serialize_struct_elt("plugin_data", &self.value.plugin_data)?;
+        serializer.serialize_struct_elt("pinned", self.value.pinned)?;
+        serializer.serialize_struct_elt("tab_group_id", self.value.tab_group_id)?;
+        serializer.serialize_struct_elt("content_scale", self.value.content_scale)?;
         Ok(None)
     }
 }
@@ -29,7 +32,7 @@ impl<'a> serde::ser::MapVisitor for DockMapVisitor<'a> {
 
 impl serde::Deserialize for Dock {
     fn deserialize<D>(deserializer: &mut D) -> Result<Dock, D::Error> where D: serde::de::Deserializer {
-        static FIELDS: &'static [&'static str] = &["handle", "plugin_name", "plugin_data"];
+        static FIELDS: &[&str] = &["handle", "plugin_name", "plugin_data", "pinned", "tab_group_id", "content_scale"];
         deserializer.deserialize_struct("Dock", FIELDS, DockVisitor)
     }
 }
@@ -43,38 +46,49 @@ impl serde::de::Visitor for DockVisitor {
         let mut handle = None;
         let mut plugin_name = None;
         let mut plugin_data = None;
+        let mut pinned = None;
+        let mut tab_group_id = None;
+        let mut content_scale = None;
 
         loop {
-            match try!(visitor.visit_key()) {
-                Some(DockField::Handle) => { handle = Some(try!(visitor.visit_value())); }
-                Some(DockField::PluginName) => { plugin_name = Some(try!(visitor.visit_value())); }
-                Some(DockField::PluginData) => { plugin_data = Some(try!(visitor.visit_value())); }
+            match visitor.visit_key()? {
+                Some(DockField::Handle) => { handle = Some(visitor.visit_value()?); }
+                Some(DockField::PluginName) => { plugin_name = Some(visitor.visit_value()?); }
+                Some(DockField::PluginData) => { plugin_data = Some(visitor.visit_value()?); }
+                Some(DockField::Pinned) => { pinned = Some(visitor.visit_value()?); }
+                Some(DockField::TabGroupId) => { tab_group_id = visitor.visit_value()?; }
+                Some(DockField::ContentScale) => { content_scale = Some(visitor.visit_value()?); }
                 None => { break; }
             }
         }
 
         let handle = match handle {
             Some(handle) => handle,
-            None => try!(visitor.missing_field("handle")),
+            None => visitor.missing_field("handle")?,
         };
 
         let plugin_name = match plugin_name {
             Some(plugin_name) => plugin_name,
-            None => try!(visitor.missing_field("plugin_name")),
+            None => visitor.missing_field("plugin_name")?,
         };
 
         let plugin_data = match plugin_data {
             Some(plugin_data) => plugin_data,
-            None => try!(visitor.missing_field("plugin_data")),
+            None => visitor.missing_field("plugin_data")?,
         };
 
-        try!(visitor.end());
+        let pinned = pinned.unwrap_or(false);
+
+        visitor.end()?;
 
         Ok(Dock {
-            handle: handle,
-            plugin_name: plugin_name,
-            plugin_data: plugin_data,
+            handle,
+            plugin_name,
+            plugin_data,
             rect: Rect::default(), // We use default here as this is always recalculated
+            pinned,
+            tab_group_id,
+            content_scale: content_scale.unwrap_or(1.0),
         })
     }
 }
@@ -83,6 +97,9 @@ enum DockField {
     Handle,
     PluginName,
     PluginData,
+    Pinned,
+    TabGroupId,
+    ContentScale,
 }
 
 impl serde::Deserialize for DockField  {
@@ -98,7 +115,10 @@ impl serde::Deserialize for DockField  {
                         "handle" => Ok(DockField::Handle),
                         "plugin_name" => Ok(DockField::PluginName),
                         "plugin_data" => Ok(DockField::PluginData),
-                        _ => Err(serde::de::Error::custom("expected handle,plugin_name or pluin_data")),
+                        "pinned" => Ok(DockField::Pinned),
+                        "tab_group_id" => Ok(DockField::TabGroupId),
+                        "content_scale" => Ok(DockField::ContentScale),
+                        _ => Err(serde::de::Error::custom("expected handle,plugin_name,pluin_data, pinned, tab_group_id or content_scale")),
                     }
                 }
         }
@@ -106,3 +126,78 @@ impl serde::Deserialize for DockField  {
         deserializer.deserialize(DockFieldVisitor)
     }
 }
+
+// Manifest entry
+
+impl serde::ser::Serialize for DockManifestEntry {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: serde::ser::Serializer {
+        serializer.serialize_struct("DockManifestEntry", DockManifestEntryMapVisitor { value: self }).map(|_| ())
+    }
+}
+
+struct DockManifestEntryMapVisitor<'a> {
+    value: &'a DockManifestEntry
+}
+
+impl<'a> serde::ser::MapVisitor for DockManifestEntryMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
+        serializer.serialize_struct_elt("handle", self.value.handle)?;
+        serializer.serialize_struct_elt("plugin_name", &self.value.plugin_name)?;
+        serializer.serialize_struct_elt("plugin_data", &self.value.plugin_data)?;
+        Ok(None)
+    }
+}
+
+impl serde::Deserialize for DockManifestEntry {
+    fn deserialize<D>(deserializer: &mut D) -> Result<DockManifestEntry, D::Error> where D: serde::de::Deserializer {
+        static FIELDS: &[&str] = &["handle", "plugin_name", "plugin_data"];
+        deserializer.deserialize_struct("DockManifestEntry", FIELDS, DockManifestEntryVisitor)
+    }
+}
+
+struct DockManifestEntryVisitor;
+
+impl serde::de::Visitor for DockManifestEntryVisitor {
+    type Value = DockManifestEntry;
+
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<DockManifestEntry, V::Error> where V: serde::de::MapVisitor {
+        let mut handle = None;
+        let mut plugin_name = None;
+        let mut plugin_data = None;
+
+        loop {
+            match visitor.visit_key()? {
+                Some(DockField::Handle) => { handle = Some(visitor.visit_value()?); }
+                Some(DockField::PluginName) => { plugin_name = Some(visitor.visit_value()?); }
+                Some(DockField::PluginData) => { plugin_data = Some(visitor.visit_value()?); }
+                Some(DockField::Pinned) => { let _: bool = visitor.visit_value()?; }
+                Some(DockField::TabGroupId) => { let _: Option<u64> = visitor.visit_value()?; }
+                Some(DockField::ContentScale) => { let _: f32 = visitor.visit_value()?; }
+                None => { break; }
+            }
+        }
+
+        let handle = match handle {
+            Some(handle) => handle,
+            None => visitor.missing_field("handle")?,
+        };
+
+        let plugin_name = match plugin_name {
+            Some(plugin_name) => plugin_name,
+            None => visitor.missing_field("plugin_name")?,
+        };
+
+        let plugin_data = match plugin_data {
+            Some(plugin_data) => plugin_data,
+            None => visitor.missing_field("plugin_data")?,
+        };
+
+        visitor.end()?;
+
+        Ok(DockManifestEntry {
+            handle,
+            plugin_name,
+            plugin_data,
+        })
+    }
+}
@@ -1,5 +1,8 @@
 use std::error::Error as StdError;
 use std::fmt;
+use area::DropRejection;
+use dock::DockHandle;
+use area::SplitHandle;
 
 /// Errors that can be return from various operatiors
 ///
@@ -7,18 +10,55 @@ use std::fmt;
 pub enum Error {
     /// Size of Workspace is incorrect
     IllegalSize(String),
+    /// Dock can't be dropped on the target
+    DropRejected(DropRejection),
+    /// Split already has the maximum number of children
+    TooManyChildren(usize),
+    /// Stored checksum doesn't match the layout data
+    ChecksumMismatch,
+    /// Layout data couldn't be parsed
+    InvalidLayout(String),
+    /// Dock with the handle is already in the workspace
+    DockExists(DockHandle),
+    /// Split has a child count outside of its `min_children`/`max_children`. Holds handle of the
+    /// split and the count
+    InvalidChildCount(SplitHandle, usize),
+    /// Dock with the handle isn't in the layout
+    DockNotFound(DockHandle),
+    /// Split with the handle doesn't exist or has no sizer at the index
+    SizerNotFound(SplitHandle, usize),
+    /// No layout stored with the name
+    LayoutNotFound(String),
 }
 
 impl StdError for Error {
     fn description(&self) -> &str {
         match *self {
             Error::IllegalSize(_) => "Illegal Size",
+            Error::DropRejected(_) => "Drop Rejected",
+            Error::TooManyChildren(_) => "Too Many Children",
+            Error::ChecksumMismatch => "Checksum Mismatch",
+            Error::InvalidLayout(_) => "Invalid Layout",
+            Error::DockExists(_) => "Dock Exists",
+            Error::InvalidChildCount(_, _) => "Invalid Child Count",
+            Error::DockNotFound(_) => "Dock Not Found",
+            Error::SizerNotFound(_, _) => "Sizer Not Found",
+            Error::LayoutNotFound(_) => "Layout Not Found",
         }
     }
 
     fn cause(&self) -> Option<&StdError> {
         match *self {
             Error::IllegalSize(_) => None,
+            Error::DropRejected(_) => None,
+            Error::TooManyChildren(_) => None,
+            Error::ChecksumMismatch => None,
+            Error::InvalidLayout(_) => None,
+            Error::DockExists(_) => None,
+            Error::InvalidChildCount(_, _) => None,
+            Error::DockNotFound(_) => None,
+            Error::SizerNotFound(_, _) => None,
+            Error::LayoutNotFound(_) => None,
         }
     }
 }
@@ -27,6 +67,15 @@ impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Error::IllegalSize(ref name) => write!(fmt, "{} {}", self.description(), name),
+            Error::DropRejected(ref reason) => write!(fmt, "Drop Rejected {:?}", reason),
+            Error::TooManyChildren(max) => write!(fmt, "Too Many Children (max {})", max),
+            Error::ChecksumMismatch => write!(fmt, "Checksum Mismatch"),
+            Error::InvalidLayout(ref reason) => write!(fmt, "Invalid Layout {}", reason),
+            Error::DockExists(handle) => write!(fmt, "Dock Exists {}", handle.0),
+            Error::InvalidChildCount(handle, count) => write!(fmt, "Invalid Child Count {} in split {}", count, handle.0),
+            Error::DockNotFound(handle) => write!(fmt, "Dock Not Found {}", handle.0),
+            Error::SizerNotFound(handle, index) => write!(fmt, "Sizer Not Found {} in split {}", index, handle.0),
+            Error::LayoutNotFound(ref name) => write!(fmt, "Layout Not Found {}", name),
         }
     }
 }
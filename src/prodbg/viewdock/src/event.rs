@@ -0,0 +1,22 @@
+use area::DragTarget;
+
+/// Notifications about changes to the layout. Workspace queues these and the host collects them
+/// with `Workspace::take_events`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum LayoutEvent {
+    /// Structure or sizes of the layout were changed by a workspace operation
+    Changed,
+    /// Progress of a drag started with `Workspace::begin_drag`
+    Drag(DragSession),
+}
+
+/// Steps of a drag. All updates between `Begin` and `End` belong to the same user action, e.g.
+/// they can be coalesced into one undo entry.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DragSession {
+    Begin(DragTarget),
+    /// Sizer moved. `ratio` is the new ratio of the sizer and `delta` the pixel delta that was
+    /// applied.
+    Update { ratio: f32, delta: (f32, f32) },
+    End,
+}
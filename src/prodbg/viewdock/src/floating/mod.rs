@@ -0,0 +1,68 @@
+mod serialize;
+
+use area::{Container, HEADER_HEIGHT};
+use dock::Dock;
+use rect::Rect;
+
+/// Container that isn't part of the tiled layout but floats on top of it
+#[derive(Debug, Clone)]
+pub struct FloatingWindow {
+    pub container: Container,
+    /// Area of the window when it isn't minimized
+    pub rect: Rect,
+    /// Minimized window only shows its title strip
+    pub minimized: bool,
+}
+
+impl FloatingWindow {
+    pub fn new(dock: Dock, rect: Rect) -> FloatingWindow {
+        FloatingWindow {
+            container: Container::new(dock, rect),
+            rect,
+            minimized: false,
+        }
+    }
+
+    /// Area currently occupied by the window. Title strip only if minimized
+    pub fn visible_rect(&self) -> Rect {
+        if self.minimized {
+            Rect::new(self.rect.x, self.rect.y, self.rect.width, HEADER_HEIGHT)
+        } else {
+            self.rect
+        }
+    }
+
+    pub fn set_minimized(&mut self, minimized: bool) {
+        self.minimized = minimized;
+        self.container.rect = self.visible_rect();
+    }
+
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.container.rect = self.visible_rect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate serde_json;
+    use {FloatingWindow, Dock, DockHandle, Rect};
+
+    #[test]
+    fn test_floating_minimized_serialize() {
+        let mut floating_in = FloatingWindow::new(Dock::new(DockHandle(3), "registers"), Rect::new(10.0, 20.0, 300.0, 200.0));
+        floating_in.set_minimized(true);
+        assert_eq!(floating_in.container.rect, Rect::new(10.0, 20.0, 300.0, 30.0));
+
+        let serialized = serde_json::to_string(&floating_in).unwrap();
+        let mut floating_out: FloatingWindow = serde_json::from_str(&serialized).unwrap();
+
+        assert!(floating_out.minimized);
+        assert_eq!(floating_out.rect, Rect::new(10.0, 20.0, 300.0, 200.0));
+        assert_eq!(floating_out.container.rect, Rect::new(10.0, 20.0, 300.0, 30.0));
+        assert_eq!(floating_out.container.docks[0].handle, DockHandle(3));
+
+        floating_out.set_minimized(false);
+        assert_eq!(floating_out.container.rect, Rect::new(10.0, 20.0, 300.0, 200.0));
+    }
+}
@@ -0,0 +1,103 @@
+extern crate serde;
+use super::FloatingWindow;
+
+// Serialization
+
+impl serde::ser::Serialize for FloatingWindow {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: serde::ser::Serializer {
+        serializer.serialize_struct("FloatingWindow", FloatingWindowMapVisitor { value: self }).map(|_| ())
+    }
+}
+
+struct FloatingWindowMapVisitor<'a> {
+    value: &'a FloatingWindow
+}
+
+impl<'a> serde::ser::MapVisitor for FloatingWindowMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
+        serializer.serialize_struct_elt("container", &self.value.container)?;
+        serializer.serialize_struct_elt("rect", self.value.rect)?;
+        serializer.serialize_struct_elt("minimized", self.value.minimized)?;
+        Ok(None)
+    }
+}
+
+// Deserialization
+
+impl serde::Deserialize for FloatingWindow {
+    fn deserialize<D>(deserializer: &mut D) -> Result<FloatingWindow, D::Error> where D: serde::de::Deserializer {
+        static FIELDS: &[&str] = &["container", "rect", "minimized"];
+        deserializer.deserialize_struct("FloatingWindow", FIELDS, FloatingWindowVisitor)
+    }
+}
+
+struct FloatingWindowVisitor;
+
+impl serde::de::Visitor for FloatingWindowVisitor {
+    type Value = FloatingWindow;
+
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<FloatingWindow, V::Error> where V: serde::de::MapVisitor {
+        let mut container = None;
+        let mut rect = None;
+        let mut minimized = None;
+
+        loop {
+            match visitor.visit_key()? {
+                Some(FloatingWindowField::Container) => { container = Some(visitor.visit_value()?); }
+                Some(FloatingWindowField::Rect) => { rect = Some(visitor.visit_value()?); }
+                Some(FloatingWindowField::Minimized) => { minimized = Some(visitor.visit_value()?); }
+                None => { break; }
+            }
+        }
+
+        let container = match container {
+            Some(container) => container,
+            None => visitor.missing_field("container")?,
+        };
+
+        let rect = match rect {
+            Some(rect) => rect,
+            None => visitor.missing_field("rect")?,
+        };
+
+        let minimized = minimized.unwrap_or(false);
+
+        visitor.end()?;
+
+        let mut res = FloatingWindow {
+            container,
+            rect,
+            minimized: false,
+        };
+        res.set_minimized(minimized); // updates container rect
+        Ok(res)
+    }
+}
+
+enum FloatingWindowField {
+    Container,
+    Rect,
+    Minimized,
+}
+
+impl serde::Deserialize for FloatingWindowField {
+    fn deserialize<D>(deserializer: &mut D) -> Result<FloatingWindowField, D::Error> where D: serde::de::Deserializer {
+        struct FloatingWindowFieldVisitor;
+
+        impl serde::de::Visitor for FloatingWindowFieldVisitor {
+            type Value = FloatingWindowField;
+
+            fn visit_str<E>(&mut self, value: &str) -> Result<FloatingWindowField, E>
+                where E: serde::de::Error {
+                    match value {
+                        "container" => Ok(FloatingWindowField::Container),
+                        "rect" => Ok(FloatingWindowField::Rect),
+                        "minimized" => Ok(FloatingWindowField::Minimized),
+                        _ => Err(serde::de::Error::custom("expected container, rect or minimized")),
+                    }
+                }
+        }
+
+        deserializer.deserialize(FloatingWindowFieldVisitor)
+    }
+}
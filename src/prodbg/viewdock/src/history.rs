@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+
+/// Undo/redo stack of serialized workspace states. History is bounded by number of entries and
+/// optionally by total size of stored states. When over a bound the oldest entries are dropped,
+/// the current state is always kept.
+#[derive(Debug)]
+pub struct LayoutHistory {
+    states: VecDeque<String>,
+    current: usize,
+    max_entries: usize,
+    max_bytes: Option<usize>,
+}
+
+impl LayoutHistory {
+    pub fn new(initial_state: String, max_entries: usize) -> LayoutHistory {
+        let mut states = VecDeque::with_capacity(max_entries);
+        states.push_back(initial_state);
+        LayoutHistory {
+            states,
+            current: 0,
+            max_entries,
+            max_bytes: None,
+        }
+    }
+
+    /// Limits total size of stored states (in bytes). `None` removes the limit
+    pub fn set_byte_budget(&mut self, max_bytes: Option<usize>) {
+        self.max_bytes = max_bytes;
+        self.prune();
+    }
+
+    /// Adds new state after the current one. States that could be redone are discarded.
+    pub fn push(&mut self, state: String) {
+        self.states.truncate(self.current + 1);
+        self.states.push_back(state);
+        self.current = self.states.len() - 1;
+        self.prune();
+    }
+
+    /// Steps back and returns the state to restore
+    pub fn undo(&mut self) -> Option<&str> {
+        if self.current == 0 {
+            return None;
+        }
+        self.current -= 1;
+        Some(&self.states[self.current])
+    }
+
+    /// Steps forward and returns the state to restore
+    pub fn redo(&mut self) -> Option<&str> {
+        if self.current + 1 >= self.states.len() {
+            return None;
+        }
+        self.current += 1;
+        Some(&self.states[self.current])
+    }
+
+    pub fn current(&self) -> &str {
+        &self.states[self.current]
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Always false as the current state is never dropped
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Total size of stored states in bytes
+    pub fn total_bytes(&self) -> usize {
+        self.states.iter().fold(0, |sum, state| sum + state.len())
+    }
+
+    fn is_over_budget(&self) -> bool {
+        self.states.len() > self.max_entries ||
+            self.max_bytes.is_some_and(|max_bytes| self.total_bytes() > max_bytes)
+    }
+
+    fn prune(&mut self) {
+        while self.current > 0 && self.is_over_budget() {
+            self.states.pop_front();
+            self.current -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {LayoutHistory, Workspace, Rect, Dock, DockHandle, Direction};
+
+    #[test]
+    fn test_history_count_limit() {
+        let mut history = LayoutHistory::new("0".to_owned(), 3);
+        for i in 1..5 {
+            history.push(i.to_string());
+        }
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.undo(), Some("3"));
+        assert_eq!(history.undo(), Some("2"));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some("3"));
+    }
+
+    #[test]
+    fn test_history_byte_budget() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        let mut history = LayoutHistory::new(ws.save_state(), 100);
+        let mut sizes = vec![ws.save_state().len()];
+        for i in 2..6 {
+            ws.split_by_dock_handle(Direction::Vertical, DockHandle(i - 1), Dock::new(DockHandle(i), "test"));
+            sizes.push(ws.save_state().len());
+        }
+        let budget = sizes[3] + sizes[4];
+        history.set_byte_budget(Some(budget));
+
+        ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        for i in 2..6 {
+            ws.split_by_dock_handle(Direction::Vertical, DockHandle(i - 1), Dock::new(DockHandle(i), "test"));
+            history.push(ws.save_state());
+            assert!(history.total_bytes() <= budget);
+        }
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.current(), ws.save_state());
+
+        history.set_byte_budget(Some(1));
+        assert_eq!(history.len(), 1);
+        assert!(!history.is_empty());
+        assert_eq!(history.current(), ws.save_state());
+    }
+}
@@ -24,21 +24,35 @@
 //!
 
 extern crate serde_json;
+
+use std::mem;
+
 #[macro_use]
 mod serialize_helper;
 mod error;
 mod rect;
 mod area;
 mod dock;
+mod floating;
 mod serialize;
+mod history;
+mod library;
+mod checksum;
+mod event;
+mod op;
 
 pub use self::error::Error;
 // use std::io::{Write, Read};
 // use std::fs::File;
 //use std::io;
-pub use rect::{Rect, Direction};
-pub use area::{Area, Split, SplitHandle, Container, DragTarget, DropTarget};
-pub use dock::{DockHandle, Dock};
+pub use rect::{Rect, Direction, Edge, EdgeSet};
+pub use area::{Area, Split, SplitHandle, SIZER_WIDTH, Container, TabPosition, DragTarget, DropTarget, DropRejection, SplitStyle, NodeCounts, SizerInfo, LayoutSnapshot, RatioNode, RatioChild};
+pub use dock::{DockHandle, Dock, DockManifestEntry};
+pub use floating::FloatingWindow;
+pub use history::LayoutHistory;
+pub use library::LayoutLibrary;
+pub use event::{LayoutEvent, DragSession};
+pub use op::LayoutOp;
 
 /// Top level structure that holds an array of all the splits and the rect size of of the full
 /// layout. This size is then propagated downwards and recalculated depending on the tree
@@ -46,15 +60,51 @@ pub use dock::{DockHandle, Dock};
 #[derive(Debug)]
 pub struct Workspace {
     pub root_area: Option<Area>,
+    /// Windows floating on top of the tiled layout. Last one is topmost
+    pub floating: Vec<FloatingWindow>,
     rect: Rect,
     /// border size of the windows (in pixels)
     pub window_border: f32,
+    /// Allows resizing the workspace by dragging its outer edges. Useful when the dock area is
+    /// embedded in a larger window. Not serialized.
+    pub root_edge_resize: bool,
+    /// Floating windows are kept but not shown or hit-tested when false
+    floating_visible: bool,
+    /// Dock that has keyboard focus. Not serialized.
+    pub focused: Option<DockHandle>,
+    /// When set, focus moves to the largest dock after a resize if the focused dock got
+    /// narrower or lower than this many pixels or isn't shown anymore. Not serialized.
+    pub focus_follows_largest: Option<f32>,
+    /// Set when layout changes and cleared by `mark_saved` or `save_for_disk`. Not serialized.
+    dirty: bool,
+    /// Events not yet collected by the host. Not serialized.
+    events: Vec<LayoutEvent>,
+    /// Target of the drag in progress. Not serialized.
+    drag: Option<DragTarget>,
+    /// Scale factor of the display. Sizer hit bands are scaled by this. Not serialized.
+    pub dpi_scale: f32,
+    /// Compact mode: sizers of splits shorter than this many pixels along their axis aren't
+    /// reported or hit-tested so they can't be resized. Not serialized.
+    pub compact_threshold: Option<f32>,
     handle_counter: SplitHandle,
+    /// Incremented by `save_for_disk` so loaders can tell which of two saves is newer
+    generation: u64,
+    /// Time of the last save if it was given to `save_for_disk`
+    saved_at: Option<u64>,
 }
 
 
 pub type ResultView<T> = std::result::Result<T, Error>;
 
+/// Reference to a dock or split that was removed by `Workspace::prune_dangling_references`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DanglingReference {
+    /// `focused` pointed at the dock
+    Focus(DockHandle),
+    /// Drag session was started on the target
+    Drag(DragTarget),
+}
+
 impl Workspace {
     /// Construct a new workspace. The rect has to be y >= 0, x >= 0, width > 0 and height > 0
     pub fn new(rect: Rect) -> std::io::Result<Workspace> {
@@ -67,18 +117,96 @@ impl Workspace {
 
         Ok(Workspace {
             root_area: None,
-            rect: rect,
+            floating: Vec::new(),
+            rect,
             window_border: 4.0,
+            root_edge_resize: false,
+            floating_visible: true,
+            dpi_scale: 1.0,
+            compact_threshold: None,
+            focused: None,
+            focus_follows_largest: None,
+            dirty: false,
+            events: Vec::new(),
+            drag: None,
             handle_counter: SplitHandle(0),
+            generation: 0,
+            saved_at: None,
         })
     }
 
+    /// Returns true if the layout has changed since it was last saved with `save_for_disk` or
+    /// flagged saved with `mark_saved`
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Clears the dirty flag, e.g. after the host has written `save_state` to disk itself
+    pub fn mark_saved(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Flags layout as changed. Operations of the workspace do this themselves, this is needed
+    /// only when `root_area` or `floating` are modified directly.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.events.push(LayoutEvent::Changed);
+    }
+
+    /// Returns events queued since the last call
+    pub fn take_events(&mut self) -> Vec<LayoutEvent> {
+        mem::take(&mut self.events)
+    }
+
+    /// Starts a drag session. Sizer moves done with `drag_sizer` until `end_drag` is called are
+    /// reported as `DragSession::Update` events.
+    pub fn begin_drag(&mut self, target: DragTarget) {
+        self.end_drag();
+        self.drag = Some(target);
+        self.events.push(LayoutEvent::Drag(DragSession::Begin(target)));
+    }
+
+    /// Ends the drag session started with `begin_drag`
+    pub fn end_drag(&mut self) {
+        if self.drag.take().is_some() {
+            self.events.push(LayoutEvent::Drag(DragSession::End));
+        }
+    }
+
+    /// Clears references that point at docks or splits that aren't in the workspace anymore, e.g.
+    /// after `root_area` was modified directly. A dangling drag session is ended. Returns the
+    /// references that were cleared.
+    pub fn prune_dangling_references(&mut self) -> Vec<DanglingReference> {
+        let mut pruned = Vec::new();
+        if let Some(focused) = self.focused {
+            if self.get_rect_by_handle(focused).is_none() {
+                self.focused = None;
+                pruned.push(DanglingReference::Focus(focused));
+            }
+        }
+        if let Some(target) = self.drag {
+            let exists = match target {
+                DragTarget::Dock(handle) => self.get_rect_by_handle(handle).is_some(),
+                DragTarget::SplitSizer(handle, index, _) => self.root_area.as_ref()
+                    .and_then(|root| root.split(handle))
+                    .is_some_and(|s| index + 1 < s.ratios.len()),
+                DragTarget::RootEdge(_) => true,
+            };
+            if !exists {
+                self.end_drag();
+                pruned.push(DanglingReference::Drag(target));
+            }
+        }
+        pruned
+    }
+
     fn next_handle(&mut self) -> SplitHandle {
         self.handle_counter.0 += 1;
         self.handle_counter
     }
 
     pub fn initialize(&mut self, dock: Dock) {
+        self.mark_dirty();
         self.root_area = Some(Area::Container(
             Container::new(
                 dock,
@@ -88,6 +216,10 @@ impl Workspace {
     }
 
     pub fn split_by_dock_handle(&mut self, direction: Direction, find_handle: DockHandle, dock: Dock) {
+        if self.root_area.as_ref().and_then(|root| root.find_container_by_dock_handle(find_handle)).is_none() {
+            return;
+        }
+        self.mark_dirty();
         let next_handle = self.next_handle();
         let is_root = match self.root_area {
             Some(Area::Container(ref c)) => c.find_dock(find_handle).is_some(),
@@ -106,8 +238,8 @@ impl Workspace {
             root.find_split_by_dock_handle(find_handle)
         });
         if let Some((parent, pos)) = parent_split {
-            if direction == parent.direction {
-                parent.append_child(pos, new_dock);
+            if direction == parent.direction && !parent.is_full() {
+                parent.append_child(pos, new_dock).unwrap();
             } else {
                 let old_child = parent.children[pos].clone();
                 let new_child = Split::from_two(direction, 0.5, next_handle, Rect::default(), old_child, new_dock);
@@ -116,38 +248,494 @@ impl Workspace {
         }
     }
 
+    /// Places layout of `other` next to the current one. Both are wrapped in a new split where the
+    /// current layout gets `ratio` of the space. Split handles of `other` are reassigned to avoid
+    /// collisions. Docks of `other` whose handles are already in use get new handles after the
+    /// largest one of both workspaces; returns (old, new) handle of each such dock.
+    pub fn merge_beside(&mut self, mut other: Workspace, direction: Direction, ratio: f32) -> Vec<(DockHandle, DockHandle)> {
+        self.mark_dirty();
+        let existing: Vec<DockHandle> = self.get_docks().iter().map(|dock| dock.handle).collect();
+        let mut next = existing.iter().cloned()
+            .chain(other.get_docks().iter().map(|dock| dock.handle))
+            .map(|handle| handle.0)
+            .max()
+            .unwrap_or(0);
+        let mut remapped = Vec::new();
+        {
+            let mut docks = other.root_area.as_mut().map_or(Vec::new(), |root| root.docks_mut());
+            docks.extend(other.floating.iter_mut().flat_map(|f| f.container.docks.iter_mut()));
+            for dock in docks {
+                if existing.contains(&dock.handle) {
+                    next += 1;
+                    remapped.push((dock.handle, DockHandle(next)));
+                    dock.handle = DockHandle(next);
+                }
+            }
+        }
+        self.floating.extend(other.floating);
+        let mut other_root = match other.root_area {
+            Some(root) => root,
+            None => return remapped,
+        };
+        self.reassign_split_handles(&mut other_root);
+        let new_root = match self.root_area.take() {
+            Some(root) => {
+                let handle = self.next_handle();
+                Area::Split(Split::from_two(direction, ratio, handle, self.rect, root, other_root))
+            },
+            None => other_root,
+        };
+        self.root_area = Some(new_root);
+        let rect = self.rect;
+        self.update(rect);
+        remapped
+    }
+
+    fn reassign_split_handles(&mut self, area: &mut Area) {
+        if let &mut Area::Split(ref mut s) = area {
+            s.handle = self.next_handle();
+            for child in s.children.iter_mut() {
+                self.reassign_split_handles(child);
+            }
+        }
+    }
+
+    /// Turns container holding `handle` into a split where it and `docks` get equal space. The
+    /// container stays first, new docks follow in the given order. If the parent split already
+    /// has `direction` and room for the docks they are added to it instead, sharing the space of
+    /// the container.
+    pub fn split_evenly(&mut self, direction: Direction, handle: DockHandle, docks: Vec<Dock>) {
+        if docks.is_empty() || self.root_area.as_ref().and_then(|root| root.find_container_by_dock_handle(handle)).is_none() {
+            return;
+        }
+        self.mark_dirty();
+        let count = docks.len();
+        let into_parent = match self.root_area.as_mut().and_then(|root| root.find_split_by_dock_handle(handle)) {
+            Some((parent, _)) => parent.direction == direction &&
+                parent.max_children.is_none_or(|max| parent.children.len() + count <= max),
+            None => false,
+        };
+        if into_parent {
+            if let Some((parent, pos)) = self.root_area.as_mut().and_then(|root| root.find_split_by_dock_handle(handle)) {
+                for (i, dock) in docks.into_iter().enumerate() {
+                    // Child at pos + i keeps its share of the space not yet given away
+                    let ratio = 1.0 / (count - i + 1) as f32;
+                    if parent.insert_child_after(pos + i, Area::Container(Container::new(dock, Rect::default())), ratio).is_err() {
+                        break;
+                    }
+                }
+            }
+            return;
+        }
+        let split_handle = self.next_handle();
+        let replace = |area: &mut Area| {
+            let mut children = vec![area.clone()];
+            children.extend(docks.into_iter().map(|dock| Area::Container(Container::new(dock, Rect::default()))));
+            let rect = area.get_rect();
+            *area = Area::Split(Split::from_children(direction, split_handle, rect, children));
+        };
+        let is_root = match self.root_area {
+            Some(Area::Container(ref c)) => c.find_dock(handle).is_some(),
+            _ => false,
+        };
+        if is_root {
+            if let Some(ref mut root) = self.root_area {
+                replace(root);
+            }
+        } else if let Some((parent, pos)) = self.root_area.as_mut().and_then(|root| root.find_split_by_dock_handle(handle)) {
+            replace(&mut parent.children[pos]);
+        }
+    }
+
+    /// Puts `dock` below the dock with `target` handle. Target keeps `ratio` of its height.
+    /// Returns false if target isn't found.
+    pub fn stack_below(&mut self, target: DockHandle, dock: Dock, ratio: f32) -> bool {
+        self.insert_after(Direction::Horizontal, target, dock, ratio)
+    }
+
+    /// Puts `dock` to the right of the dock with `target` handle. Target keeps `ratio` of its
+    /// width. Returns false if target isn't found.
+    pub fn stack_right(&mut self, target: DockHandle, dock: Dock, ratio: f32) -> bool {
+        self.insert_after(Direction::Vertical, target, dock, ratio)
+    }
+
+    /// Returns edges of the dock that can be dragged to resize it. Outer edges of the workspace
+    /// are included when `root_edge_resize` is enabled. See `Area::resizable_edges`.
+    pub fn resizable_edges(&self, handle: DockHandle) -> EdgeSet {
+        let root = match self.root_area {
+            Some(ref root) => root,
+            None => return EdgeSet::default(),
+        };
+        let mut edges = root.resizable_edges(handle);
+        if self.root_edge_resize {
+            if let Some(rect) = root.find_container_by_dock_handle(handle).map(|c| c.rect) {
+                let epsilon = 0.5;
+                if (rect.x - self.rect.x).abs() < epsilon {
+                    edges.insert(Edge::Left);
+                }
+                if (rect.x + rect.width - (self.rect.x + self.rect.width)).abs() < epsilon {
+                    edges.insert(Edge::Right);
+                }
+                if (rect.y - self.rect.y).abs() < epsilon {
+                    edges.insert(Edge::Top);
+                }
+                if (rect.y + rect.height - (self.rect.y + self.rect.height)).abs() < epsilon {
+                    edges.insert(Edge::Bottom);
+                }
+            }
+        }
+        edges
+    }
+
+    /// Wraps the container holding `handle` in a new single-child split with `direction` so
+    /// siblings can be added next to it. See `Area::wrap_child_in_split`. Returns false if the
+    /// dock isn't found.
+    pub fn wrap_child_in_split(&mut self, handle: DockHandle, direction: Direction) -> bool {
+        if self.root_area.as_ref().and_then(|root| root.find_container_by_dock_handle(handle)).is_none() {
+            return false;
+        }
+        let split_handle = self.next_handle();
+        if let Some(ref mut root) = self.root_area {
+            root.wrap_child_in_split(handle, direction, split_handle);
+        }
+        self.mark_dirty();
+        true
+    }
+
+    /// Puts `dock` at `edge` of split `handle` spanning all of its children. See
+    /// `Area::add_spanning`. Returns false if the split isn't found.
+    pub fn add_spanning(&mut self, handle: SplitHandle, dock: Dock, edge: Edge, ratio: f32) -> bool {
+        let new_handle = self.next_handle();
+        let added = self.root_area.as_mut().is_some_and(|root| root.add_spanning(handle, dock, edge, ratio, new_handle));
+        if added {
+            self.mark_dirty();
+        }
+        added
+    }
+
+    /// Puts `dock` at `edge` of the dock with `target` handle so that it gets `pixels` of the
+    /// space of the target (width for Left/Right, height for Top/Bottom). Returns false if target
+    /// isn't found.
+    pub fn insert_with_size(&mut self, target: DockHandle, dock: Dock, edge: Edge, pixels: f32) -> bool {
+        let rect = match self.root_area.as_ref().and_then(|root| root.find_container_by_dock_handle(target)) {
+            Some(c) => c.rect,
+            None => return false,
+        };
+        let direction = match edge {
+            Edge::Left | Edge::Right => Direction::Vertical,
+            Edge::Top | Edge::Bottom => Direction::Horizontal,
+        };
+        let ratio = 1.0 - rect.ratio_for_pixels(direction, pixels);
+        let res = self.insert_area(target, Area::Container(Container::new(dock, Rect::default())), edge, ratio);
+        let rect = self.rect;
+        self.update(rect);
+        res
+    }
+
+    fn insert_after(&mut self, direction: Direction, target: DockHandle, dock: Dock, ratio: f32) -> bool {
+        let edge = match direction {
+            Direction::Vertical => Edge::Right,
+            Direction::Horizontal => Edge::Bottom,
+        };
+        self.insert_area(target, Area::Container(Container::new(dock, Rect::default())), edge, ratio)
+    }
+
+    /// Puts `area` at `edge` of the container holding `target`. Target keeps `ratio` of its
+    /// space. Returns false if target isn't found.
+    fn insert_area(&mut self, target: DockHandle, area: Area, edge: Edge, ratio: f32) -> bool {
+        let found = self.root_area.as_ref().is_some_and(|root| root.dock(target).is_some());
+        if !found {
+            return false;
+        }
+        self.mark_dirty();
+        let (direction, before) = match edge {
+            Edge::Left => (Direction::Vertical, true),
+            Edge::Right => (Direction::Vertical, false),
+            Edge::Top => (Direction::Horizontal, true),
+            Edge::Bottom => (Direction::Horizontal, false),
+        };
+        let next_handle = self.next_handle();
+        let wrap = |old: Area, rect: Rect| if before {
+            Split::from_two(direction, 1.0 - ratio, next_handle, rect, area.clone(), old)
+        } else {
+            Split::from_two(direction, ratio, next_handle, rect, old, area.clone())
+        };
+        let is_root = match self.root_area {
+            Some(Area::Container(ref c)) => c.find_dock(target).is_some(),
+            _ => false,
+        };
+        if is_root {
+            let old_root = self.root_area.take().unwrap();
+            self.root_area = Some(Area::Split(wrap(old_root, self.rect)));
+            return true;
+        }
+        let parent_split = self.root_area.as_mut().and_then(|root| {
+            root.find_split_by_dock_handle(target)
+        });
+        match parent_split {
+            Some((parent, pos)) => {
+                if parent.direction == direction && !parent.is_full() {
+                    if before {
+                        parent.insert_child_after(pos, area.clone(), 1.0 - ratio).unwrap();
+                        parent.children.swap(pos, pos + 1);
+                        let rect = parent.rect;
+                        parent.update_rect(rect);
+                    } else {
+                        parent.insert_child_after(pos, area.clone(), ratio).unwrap();
+                    }
+                } else {
+                    let old_child = parent.children[pos].clone();
+                    parent.replace_child(pos, Area::Split(wrap(old_child, Rect::default())));
+                }
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Inserts subtree saved with `Area::serialize_subtree` at `edge` of the drop target. Splits
+    /// of the subtree get new handles. Docks keep their handles so pasting fails with
+    /// `Error::DockExists` if any of them is already in the workspace.
+    pub fn paste_subtree(&mut self, data: &str, target: &DropTarget, edge: Edge) -> ResultView<()> {
+        let mut area: Area = serde_json::from_str(data).map_err(|e| Error::InvalidLayout(format!("{}", e)))?;
+        let existing = self.get_docks();
+        for (handle, _, _) in area.leaves_with_depth() {
+            if existing.iter().any(|dock| dock.handle == handle) {
+                return Err(Error::DockExists(handle));
+            }
+        }
+        self.reassign_split_handles(&mut area);
+        let target_handle = match target {
+            &DropTarget::Dock(handle) => handle,
+        };
+        if !self.insert_area(target_handle, area, edge, 0.5) {
+            return Err(Error::DropRejected(DropRejection::TargetNotFound));
+        }
+        let rect = self.rect;
+        self.update(rect);
+        Ok(())
+    }
+
+    /// Adds floating window on top of all others
+    pub fn add_floating(&mut self, dock: Dock, rect: Rect) {
+        self.mark_dirty();
+        self.floating.push(FloatingWindow::new(dock, rect));
+    }
+
+    /// Finds floating window that holds dock with supplied handle
+    pub fn find_floating_mut(&mut self, handle: DockHandle) -> Option<&mut FloatingWindow> {
+        self.floating.iter_mut().find(|f| f.container.find_dock(handle).is_some())
+    }
+
+    /// Shows or hides all floating windows at once. Hidden windows are kept and reappear when
+    /// made visible again. The setting is saved with the layout.
+    pub fn set_floating_visible(&mut self, visible: bool) {
+        if self.floating_visible != visible {
+            self.floating_visible = visible;
+            self.mark_dirty();
+        }
+    }
+
+    pub fn is_floating_visible(&self) -> bool {
+        self.floating_visible
+    }
+
+    /// Floating windows that should be rendered, bottom-most first
+    pub fn visible_floating<'a>(&'a self) -> impl Iterator<Item=&'a FloatingWindow> + 'a {
+        let count = if self.floating_visible { self.floating.len() } else { 0 };
+        self.floating.iter().take(count)
+    }
+
+    /// Counts nodes of the tiled layout and floating windows
+    pub fn node_counts(&self) -> NodeCounts {
+        let mut counts = self.root_area.as_ref().map_or(NodeCounts::default(), |root| root.node_counts());
+        counts.floating = self.floating.len();
+        counts.containers += self.floating.len();
+        counts.docks += self.floating.iter().fold(0, |sum, f| sum + f.container.docks.len());
+        counts
+    }
+
     pub fn get_rect_by_handle(&self, handle: DockHandle) -> Option<Rect> {
-        self.root_area.as_ref().and_then(|area| {
-            area.find_container_by_dock_handle(handle).and_then(|container| {
-                Some(container.rect.clone())
-            })
-        })
+        self.floating.iter()
+            .find(|f| f.container.find_dock(handle).is_some())
+            .map(|f| f.container.rect)
+            .or_else(|| self.root_area.as_ref().and_then(|area| {
+                area.find_container_by_dock_handle(handle).map(|container| container.rect)
+            }))
     }
 
     pub fn get_hover_dock(&self, pos: (f32, f32)) -> Option<DockHandle> {
+        self.floating.iter().rev()
+            .filter(|_| self.floating_visible)
+            .filter_map(|f| f.container.get_dock_handle_at_pos(pos))
+            .next()
+            .or_else(|| self.root_area.as_ref().and_then(|root| {
+                root.get_dock_handle_at_pos(pos)
+            }))
+    }
+
+    /// Like `get_hover_dock` but falls back to the closest dock when the position doesn't hit any
+    pub fn get_nearest_dock(&self, pos: (f32, f32)) -> Option<DockHandle> {
         self.root_area.as_ref().and_then(|root| {
-            root.get_dock_handle_at_pos(pos)
+            root.nearest_dock_at_pos(pos)
         })
     }
 
+    /// Picks sizer that keyboard resizing of the focused dock should move. With `grow` the sizer
+    /// after the focused area (right or below) is used and the one before it otherwise. Returns
+    /// None if nothing is focused or there is no neighbor in that direction.
+    pub fn sizer_for_focus(&self, dir: Direction, grow: bool) -> Option<(SplitHandle, usize)> {
+        match (self.focused, self.root_area.as_ref()) {
+            (Some(focused), Some(root)) => root.sizer_next_to_dock(focused, dir, grow),
+            _ => None,
+        }
+    }
+
     pub fn update(&mut self, new_rect: Rect) {
         self.rect = new_rect;
         if let Some(ref mut a) = self.root_area {
             a.update_rect(new_rect);
         }
+        self.apply_focus_policy();
     }
 
-    pub fn drag_sizer(&mut self, handle: SplitHandle, index: usize, delta: (f32, f32)) {
-        if let Some(ref mut root) = self.root_area {
-            if let Some(s) = root.find_split_by_handle(handle) {
-                s.change_ratio(index, delta);
+    /// Same as `update` but returns true if any dock moved or changed size so the caller can skip
+    /// redrawing when nothing visible changed.
+    pub fn resize(&mut self, new_rect: Rect) -> bool {
+        let before = self.leaf_rects();
+        self.update(new_rect);
+        let after = self.leaf_rects();
+        before.len() != after.len() ||
+            before.iter().zip(after.iter()).any(|(&(handle, ref rect), &(other_handle, ref other_rect))| {
+                handle != other_handle || !rect.approx_eq(other_rect, 0.001)
+            })
+    }
+
+    fn leaf_rects(&self) -> Vec<(DockHandle, Rect)> {
+        self.root_area.iter()
+            .flat_map(|root| root.leaves_with_depth())
+            .map(|(handle, rect, _)| (handle, rect))
+            .collect()
+    }
+
+    fn apply_focus_policy(&mut self) {
+        let min_size = match self.focus_follows_largest {
+            Some(min_size) => min_size,
+            None => return,
+        };
+        let focused = match self.focused {
+            Some(focused) => focused,
+            None => return,
+        };
+        if self.visible_floating().any(|f| !f.minimized && f.container.active_dock().map(|d| d.handle) == Some(focused)) {
+            return;
+        }
+        let root = match self.root_area {
+            Some(ref root) => root,
+            None => return,
+        };
+        let usable = root.visible_docks().iter().any(|dock| dock.handle == focused) &&
+            root.find_container_by_dock_handle(focused)
+                .is_some_and(|c| c.rect.width >= min_size && c.rect.height >= min_size);
+        if !usable {
+            self.focused = root.largest_dock();
+        }
+    }
+
+    /// Moves sizer of a split. Returns true if the layout changed
+    pub fn drag_sizer(&mut self, handle: SplitHandle, index: usize, delta: (f32, f32)) -> bool {
+        let new_ratio = match self.root_area {
+            Some(ref mut root) => root.find_split_by_handle(handle)
+                .and_then(|s| if s.change_ratio(index, delta) { Some(s.ratios[index]) } else { None }),
+            None => None,
+        };
+        let changed = new_ratio.is_some();
+        if let Some(ratio) = new_ratio {
+            self.mark_dirty();
+            if self.drag.is_some() {
+                self.events.push(LayoutEvent::Drag(DragSession::Update { ratio, delta }));
             }
+            self.apply_focus_policy();
+        }
+        changed
+    }
+
+    /// Resizes the workspace by moving one of its outer edges. Delta uses the same convention as
+    /// in `drag_sizer` (previous position minus current position)
+    pub fn drag_root_edge(&mut self, edge: Edge, delta: (f32, f32)) {
+        let mut rect = self.rect;
+        match edge {
+            Edge::Left => {
+                let dx = delta.0.min(rect.x).max(1.0 - rect.width);
+                rect.x -= dx;
+                rect.width += dx;
+            },
+            Edge::Right => rect.width = (rect.width - delta.0).max(1.0),
+            Edge::Top => {
+                let dy = delta.1.min(rect.y).max(1.0 - rect.height);
+                rect.y -= dy;
+                rect.height += dy;
+            },
+            Edge::Bottom => rect.height = (rect.height - delta.1).max(1.0),
+        }
+        if rect != self.rect {
+            self.mark_dirty();
         }
+        self.update(rect);
+    }
+
+    fn get_root_edge_at_pos(&self, pos: (f32, f32)) -> Option<Edge> {
+        if !self.root_edge_resize {
+            return None;
+        }
+        [Edge::Left, Edge::Right, Edge::Top, Edge::Bottom].iter()
+            .find(|&&edge| self.rect.area_around_edge(edge, self.sizer_width()).point_is_inside(pos)).copied()
+    }
+
+    /// Width (in pixels) of the band used for grabbing sizers and edges with `dpi_scale` applied.
+    /// Renderers should use this for the sizer width as well.
+    pub fn sizer_width(&self) -> f32 {
+        SIZER_WIDTH * self.dpi_scale
     }
 
     pub fn get_drag_target_at_pos(&self, pos: (f32, f32)) -> Option<DragTarget> {
-        self.root_area.as_ref().and_then(|root| {
-            root.get_drag_target_at_pos(pos)
+        if let Some(edge) = self.get_root_edge_at_pos(pos) {
+            return Some(DragTarget::RootEdge(edge));
+        }
+        self.root_area.as_ref()
+            .and_then(|root| root.get_drag_target_at_pos(pos, self.sizer_width()))
+            .and_then(|target| match target {
+                DragTarget::SplitSizer(handle, _, _) if !self.is_resizable(handle) => None,
+                _ => Some(target),
+            })
+    }
+
+    /// Returns all sizers that can be dragged, i.e. the ones the renderer should draw. Sizers of
+    /// splits below `compact_threshold` are left out.
+    pub fn all_sizers(&self) -> Vec<SizerInfo> {
+        self.root_area.as_ref().map_or(Vec::new(), |root| {
+            root.all_sizers(self.sizer_width())
+                .into_iter()
+                .filter(|sizer| self.is_resizable(sizer.handle))
+                .collect()
+        })
+    }
+
+    /// Number of sizers `all_sizers` returns
+    pub fn sizer_count(&self) -> usize {
+        self.all_sizers().len()
+    }
+
+    fn is_resizable(&self, handle: SplitHandle) -> bool {
+        let threshold = match self.compact_threshold {
+            Some(threshold) => threshold,
+            None => return true,
+        };
+        self.root_area.as_ref().and_then(|root| root.split(handle)).is_some_and(|s| {
+            match s.direction {
+                Direction::Vertical => s.rect.width >= threshold,
+                Direction::Horizontal => s.rect.height >= threshold,
+            }
         })
     }
 
@@ -157,7 +745,62 @@ impl Workspace {
         })
     }
 
+    /// Checks if `dragging` dock can be dropped on `target`. Should be called before the drop is
+    /// committed so the UI can show why the drop isn't possible.
+    pub fn validate_drop(&self, dragging: DockHandle, target: &DropTarget) -> Result<(), DropRejection> {
+        let root = match self.root_area {
+            Some(ref root) => root,
+            None => return Err(DropRejection::DockNotFound),
+        };
+        if root.find_container_by_dock_handle(dragging).is_none() {
+            return Err(DropRejection::DockNotFound);
+        }
+        match target {
+            &DropTarget::Dock(target_handle) => {
+                let container = match root.find_container_by_dock_handle(target_handle) {
+                    Some(c) => c,
+                    None => return Err(DropRejection::TargetNotFound),
+                };
+                if container.find_dock(dragging).is_some() {
+                    return Err(DropRejection::SelfDrop);
+                }
+                if !container.accepts_drops {
+                    return Err(DropRejection::TargetLocked);
+                }
+            }
+        }
+        // The container goes away with its last dock
+        let source_emptied = root.find_container_by_dock_handle(dragging).is_some_and(|c| c.docks.len() == 1);
+        let parent = root.split_path_to_dock(dragging)
+            .and_then(|path| path.last().cloned())
+            .and_then(|handle| root.split(handle));
+        if let Some(parent) = parent {
+            let too_few = parent.min_children.is_some_and(|min| parent.children.len() - 1 < min);
+            if source_emptied && too_few {
+                return Err(DropRejection::BelowMinChildren);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops `dragging` dock on `target`. Target is resolved against the current tree so a target
+    /// found before the tree changed is either applied to the current location of the target
+    /// dock or rejected.
+    pub fn commit_drop(&mut self, dragging: DockHandle, target: &DropTarget) -> ResultView<()> {
+        self.validate_drop(dragging, target).map_err(Error::DropRejected)?;
+        match target {
+            &DropTarget::Dock(target_handle) => {
+                self.swap_docks(dragging, target_handle);
+            }
+        }
+        Ok(())
+    }
+
     pub fn delete_by_handle(&mut self, handle: DockHandle) {
+        if self.root_area.as_ref().and_then(|root| root.find_container_by_dock_handle(handle)).is_none() {
+            return;
+        }
+        self.mark_dirty();
         let mut should_delete_root = false;
         if let Some(Area::Container(ref c)) = self.root_area {
             should_delete_root = c.find_dock(handle).is_some();
@@ -196,38 +839,229 @@ impl Workspace {
         }
     }
 
-    pub fn swap_docks(&mut self, first: DockHandle, second: DockHandle) {
-        if let Some(ref mut root) = self.root_area {
-            if first == second {
-                return;
-            }
-            let first_copy;
-            let second_copy;
-            let first_split_handle;
-            let first_dock_index;
-            match root.find_split_by_dock_handle(first) {
-                None => return,
-                Some((parent, index)) => {
-                    first_copy = parent.children[index].clone();
-                    first_dock_index = index;
-                    first_split_handle = parent.handle;
+    /// Returns a copy of the workspace that only has the docks in `keep`, e.g. for a focused
+    /// session. Remaining docks share the space of the removed ones (see `Area::filtered`). The
+    /// workspace itself isn't changed.
+    pub fn filtered_view(&self, keep: &[DockHandle]) -> Workspace {
+        let mut view = Workspace::new(self.rect).unwrap();
+        view.window_border = self.window_border;
+        view.dpi_scale = self.dpi_scale;
+        view.handle_counter = self.handle_counter;
+        view.root_area = self.root_area.as_ref().and_then(|root| root.filtered(keep));
+        view.floating = self.floating.iter()
+            .filter_map(|f| {
+                let mut res = f.clone();
+                res.container.docks.retain(|dock| keep.contains(&dock.handle));
+                if res.container.docks.is_empty() {
+                    None
+                } else {
+                    Some(res)
                 }
+            })
+            .collect();
+        view.focused = self.focused.filter(|handle| keep.contains(handle));
+        let rect = self.rect;
+        view.update(rect);
+        view
+    }
+
+    /// Reduces the layout to the minimal structure with the same look. See `Area::simplify`
+    pub fn simplify(&mut self) {
+        let (changed, is_empty) = match self.root_area {
+            Some(ref mut root) => (root.simplify(), root.is_empty()),
+            None => (false, false),
+        };
+        if is_empty {
+            self.root_area = None;
+        }
+        if changed || is_empty {
+            self.mark_dirty();
+        }
+        let rect = self.rect;
+        self.update(rect);
+    }
+
+    /// Swaps the dock with its neighbor at `edge` (see `Area::neighbor_dock`). Both containers
+    /// keep their sizes. Returns false if there is no neighbor on that side.
+    pub fn swap_with_neighbor(&mut self, dock: DockHandle, edge: Edge) -> bool {
+        let neighbor = self.root_area.as_ref().and_then(|root| root.neighbor_dock(dock, edge));
+        match neighbor {
+            Some(neighbor) => self.swap_docks(dock, neighbor),
+            None => false,
+        }
+    }
+
+    /// Exchanges positions of two docks anywhere in the tree. Containers (and so sizes) stay in
+    /// place, only docks move between them. Returns false if any of the docks isn't found.
+    pub fn swap_docks(&mut self, first: DockHandle, second: DockHandle) -> bool {
+        let swapped = match self.root_area {
+            Some(ref mut root) => {
+                let mut docks = root.docks_mut();
+                let first_index = docks.iter().position(|dock| dock.handle == first);
+                let second_index = docks.iter().position(|dock| dock.handle == second);
+                match (first_index, second_index) {
+                    (Some(a), Some(b)) => {
+                        if a != b {
+                            let (low, high) = docks.split_at_mut(a.max(b));
+                            mem::swap(&mut *low[a.min(b)], &mut *high[0]);
+                        }
+                        true
+                    },
+                    _ => false,
+                }
+            },
+            None => false,
+        };
+        if swapped && first != second {
+            self.mark_dirty();
+        }
+        swapped
+    }
+
+    /// Applies `ops` in order. If any of them fails the layout is restored to the state before
+    /// the batch and the error of the failed operation is returned.
+    pub fn apply_batch(&mut self, ops: Vec<LayoutOp>) -> ResultView<()> {
+        let root_area = self.root_area.clone();
+        let floating = self.floating.clone();
+        let handle_counter = self.handle_counter;
+        let focused = self.focused;
+        let dirty = self.dirty;
+        let event_count = self.events.len();
+
+        for op in ops {
+            if let Err(e) = self.apply_op(op) {
+                self.root_area = root_area;
+                self.floating = floating;
+                self.handle_counter = handle_counter;
+                self.focused = focused;
+                self.dirty = dirty;
+                self.events.truncate(event_count);
+                return Err(e);
             }
-            match root.find_split_by_dock_handle(second) {
-                None => return,
-                Some((parent, index)) => second_copy = parent.replace_child(index, first_copy),
-            }
-            match root.find_split_by_handle(first_split_handle) {
-                None => panic!("Tried to swap docks {:?} and {:?} but lost {:?} in the middle", first, second, first),
-                Some(s) => {s.replace_child(first_dock_index, second_copy);},
-            }
+            let rect = self.rect;
+            self.update(rect);
         }
+        Ok(())
     }
 
+    fn apply_op(&mut self, op: LayoutOp) -> ResultView<()> {
+        let has_dock = |ws: &Workspace, handle| ws.root_area.as_ref().is_some_and(|root| root.dock(handle).is_some());
+        match op {
+            LayoutOp::Split { direction, target, dock } => {
+                if !has_dock(self, target) {
+                    return Err(Error::DockNotFound(target));
+                }
+                self.split_by_dock_handle(direction, target, dock);
+            },
+            LayoutOp::StackRight { target, dock, ratio } => if !self.stack_right(target, dock, ratio) {
+                return Err(Error::DockNotFound(target));
+            },
+            LayoutOp::StackBelow { target, dock, ratio } => if !self.stack_below(target, dock, ratio) {
+                return Err(Error::DockNotFound(target));
+            },
+            LayoutOp::DragSizer { handle, index, delta } => {
+                let valid = self.root_area.as_ref()
+                    .and_then(|root| root.split(handle))
+                    .is_some_and(|s| index + 1 < s.ratios.len());
+                if !valid {
+                    return Err(Error::SizerNotFound(handle, index));
+                }
+                self.drag_sizer(handle, index, delta);
+            },
+            LayoutOp::Drop { dragging, target } => self.commit_drop(dragging, &target)?,
+            LayoutOp::Swap(first, second) => {
+                for &handle in &[first, second] {
+                    if !has_dock(self, handle) {
+                        return Err(Error::DockNotFound(handle));
+                    }
+                }
+                self.swap_docks(first, second);
+            },
+            LayoutOp::Delete(handle) => {
+                if !has_dock(self, handle) {
+                    return Err(Error::DockNotFound(handle));
+                }
+                self.delete_by_handle(handle);
+            },
+        }
+        Ok(())
+    }
+
+    /// Serializes the workspace. Doesn't change the dirty flag as this is also used for undo
+    /// snapshots.
     pub fn save_state(&self) -> String {
         serde_json::to_string(self).unwrap()
     }
 
+    /// Serializes the workspace for writing to disk. Unlike `save_state`, which is also used for
+    /// undo snapshots, this increments the generation and stores `timestamp` (e.g. seconds since
+    /// the Unix epoch) with the layout. Clears the dirty flag.
+    pub fn save_for_disk(&mut self, timestamp: Option<u64>) -> String {
+        self.generation += 1;
+        self.saved_at = timestamp;
+        self.dirty = false;
+        self.save_state()
+    }
+
+    /// Number of times this layout has been saved with `save_for_disk`. Loaded layouts continue
+    /// from the stored value.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Timestamp given to `save_for_disk` when the layout was last saved
+    pub fn saved_at(&self) -> Option<u64> {
+        self.saved_at
+    }
+
+    /// Returns true if this layout was saved fewer times than `other`, e.g. when another
+    /// instance has saved the same layout after this one was loaded.
+    pub fn is_older_than(&self, other: &Workspace) -> bool {
+        self.generation() < other.generation()
+    }
+
+    /// Like `save_state` but the layout is preceded by a line with CRC-32 of it so corrupted files
+    /// can be detected with `from_state_checked`.
+    pub fn save_state_with_checksum(&self) -> String {
+        let state = self.save_state();
+        format!("{:08x}\n{}", checksum::crc32(state.as_bytes()), state)
+    }
+
+    /// Checks constraints of the layout that loading doesn't enforce, e.g. child counts of
+    /// splits. Meant for layouts that may have been edited outside of the application.
+    pub fn validate(&self) -> ResultView<()> {
+        self.root_area.as_ref().map_or(Ok(()), |root| root.validate())
+    }
+
+    /// Loads layout saved with `save_state_with_checksum`. Returns `Error::ChecksumMismatch` if
+    /// the data has been changed or truncated and `Error::InvalidChildCount` if a split breaks
+    /// its child count limits (see `validate`).
+    pub fn from_state_checked(data: &str) -> ResultView<Workspace> {
+        let mut parts = data.splitn(2, '\n');
+        let checksum = parts.next().and_then(|line| u32::from_str_radix(line, 16).ok());
+        let state = parts.next();
+        match (checksum, state) {
+            (Some(checksum), Some(state)) if checksum == checksum::crc32(state.as_bytes()) => {
+                let ws: Workspace = serde_json::from_str(state).map_err(|e| Error::InvalidLayout(format!("{}", e)))?;
+                ws.validate()?;
+                Ok(ws)
+            },
+            _ => Err(Error::ChecksumMismatch),
+        }
+    }
+
+    /// Serializes workspace to pretty printed JSON with keys in sorted order. Same layouts always
+    /// produce the same text which keeps diffs small when layouts are kept in version control.
+    pub fn to_pretty_json(&self) -> String {
+        // Going through the compact text keeps newtype handles as plain numbers. Value stores
+        // objects in BTreeMap so keys come out sorted.
+        let value: serde_json::Value = serde_json::from_str(&self.save_state()).unwrap();
+        serde_json::to_string_pretty(&value).unwrap()
+    }
+
+    /// Loads layout saved with `save_state`, e.g. an undo snapshot. Panics if the text isn't a
+    /// layout. Child count limits aren't checked; use `from_state_checked` or `validate` for
+    /// layouts that may have been edited outside the program.
     pub fn from_state(state: &str) -> Workspace {
         serde_json::from_str(state).unwrap()
     }
@@ -238,9 +1072,18 @@ impl Workspace {
             Some(ref root) => Workspace::collect_docks(&mut docks, root),
             None => {},
         };
+        for f in &self.floating {
+            docks.extend(f.container.docks.iter().cloned());
+        }
         return docks;
     }
 
+    /// Lists all docks (including tabs and floating windows) without any geometry. Useful for
+    /// restoring the open views into a different layout.
+    pub fn dock_manifest(&self) -> Vec<DockManifestEntry> {
+        self.get_docks().iter().map(DockManifestEntry::from_dock).collect()
+    }
+
     fn collect_docks(target: &mut Vec<Dock>, source: &Area) {
         match *source {
             Area::Container(ref c) => {
@@ -259,7 +1102,7 @@ impl Workspace {
 //    pub fn save(&self, file_name: &str) -> io::Result<()> {
 //        unimplemented!();
 //        let data = serde_json::to_string_pretty(self).unwrap_or("".to_owned());
-//        let mut f = try!(File::create(file_name));
+//        let mut f = File::create(file_name)?;
 //        let _ = f.write_all(data.as_bytes());
 //        println!("saved file");
 //        Ok(())
@@ -282,15 +1125,29 @@ impl Workspace {
 mod test {
     extern crate serde_json;
 
-    use {Area, Container, Workspace, Dock, Rect, DockHandle, SplitHandle};
+    use {Area, Container, Workspace, Dock, Rect, DockHandle, SplitHandle, DragTarget, Edge, Direction};
+    use {DropTarget, DropRejection, FloatingWindow, NodeCounts, Error, SIZER_WIDTH};
+    use {DockManifestEntry, LayoutEvent, DragSession, LayoutOp, DanglingReference};
 
     #[test]
     fn test_workspace_serialize_0() {
         let ws_in = Workspace {
             root_area: None,
+            floating: Vec::new(),
             rect: Rect::new(4.0, 5.0, 2.0, 8.0),
             window_border: 6.0,
+            root_edge_resize: false,
+            floating_visible: true,
+            dpi_scale: 1.0,
+            compact_threshold: None,
+            focused: None,
+            focus_follows_largest: None,
+            dirty: false,
+            events: Vec::new(),
+            drag: None,
             handle_counter: SplitHandle(2),
+            generation: 0,
+            saved_at: None,
         };
 
         let serialized = serde_json::to_string(&ws_in).unwrap();
@@ -311,9 +1168,21 @@ mod test {
             root_area: Some(Area::Container(
                 Container::new(Dock::new(DockHandle(5), "test"), Rect::default())
             )),
+            floating: vec![FloatingWindow::new(Dock::new(DockHandle(6), "float"), Rect::new(1.0, 2.0, 3.0, 4.0))],
             rect: Rect::new(4.0, 5.0, 2.0, 8.0),
             window_border: 6.0,
+            root_edge_resize: false,
+            floating_visible: true,
+            dpi_scale: 1.0,
+            compact_threshold: None,
+            focused: None,
+            focus_follows_largest: None,
+            dirty: false,
+            events: Vec::new(),
+            drag: None,
             handle_counter: SplitHandle(2),
+            generation: 0,
+            saved_at: None,
         };
 
         let serialized = serde_json::to_string(&ws_in).unwrap();
@@ -323,5 +1192,764 @@ mod test {
             Some(Area::Container(_)) => true,
             _ => false,
         });
+        assert_eq!(ws_out.floating.len(), 1);
+        assert_eq!(ws_out.floating[0].rect, Rect::new(1.0, 2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn test_insert_with_size() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 500.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.update(Rect::new(0.0, 0.0, 1000.0, 500.0));
+        assert!(ws.insert_with_size(DockHandle(1), Dock::new(DockHandle(2), "registers"), Edge::Left, 200.0));
+        let container = ws.root_area.as_ref().unwrap().find_container_by_dock_handle(DockHandle(2)).unwrap();
+        assert!(container.rect.approx_eq(&Rect::new(0.0, 0.0, 200.0, 500.0), 0.01));
+
+        assert!(ws.insert_with_size(DockHandle(1), Dock::new(DockHandle(3), "memory"), Edge::Bottom, 100.0));
+        let container = ws.root_area.as_ref().unwrap().find_container_by_dock_handle(DockHandle(3)).unwrap();
+        assert!(container.rect.approx_eq(&Rect::new(200.0, 400.0, 800.0, 100.0), 0.01));
+        assert!(!ws.insert_with_size(DockHandle(9), Dock::new(DockHandle(4), "memory"), Edge::Top, 100.0));
+
+        // Asking for more than the target has leaves it a sliver instead of collapsing it
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 500.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.update(Rect::new(0.0, 0.0, 1000.0, 500.0));
+        assert!(ws.insert_with_size(DockHandle(1), Dock::new(DockHandle(2), "registers"), Edge::Left, 1200.0));
+        assert!((ws.get_rect_by_handle(DockHandle(1)).unwrap().width - 10.0).abs() < 0.01);
+        assert!((ws.get_rect_by_handle(DockHandle(2)).unwrap().width - 990.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_apply_batch_rollback() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        let state = ws.save_state();
+        ws.mark_saved();
+        ws.take_events();
+
+        let ops = vec![
+            LayoutOp::StackRight { target: DockHandle(1), dock: Dock::new(DockHandle(2), "registers"), ratio: 0.5 },
+            LayoutOp::Split { direction: Direction::Horizontal, target: DockHandle(2), dock: Dock::new(DockHandle(3), "memory") },
+            LayoutOp::Swap(DockHandle(1), DockHandle(3)),
+            LayoutOp::Delete(DockHandle(7)),
+        ];
+        match ws.apply_batch(ops) {
+            Err(Error::DockNotFound(DockHandle(7))) => {},
+            other => panic!("expected DockNotFound, got {:?}", other),
+        }
+        assert_eq!(ws.save_state(), state);
+        assert!(!ws.is_dirty());
+        assert!(ws.take_events().is_empty());
+
+        let ops = vec![
+            LayoutOp::StackRight { target: DockHandle(1), dock: Dock::new(DockHandle(2), "registers"), ratio: 0.5 },
+            LayoutOp::DragSizer { handle: SplitHandle(1), index: 0, delta: (-100.0, 0.0) },
+        ];
+        ws.apply_batch(ops).unwrap();
+        assert_eq!(ws.get_rect_by_handle(DockHandle(2)).unwrap().width, 100.0);
+    }
+
+    #[test]
+    fn test_prune_dangling_references() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "registers"));
+        ws.add_floating(Dock::new(DockHandle(3), "memory"), Rect::new(10.0, 10.0, 100.0, 100.0));
+        ws.focused = Some(DockHandle(2));
+        ws.begin_drag(DragTarget::SplitSizer(SplitHandle(1), 0, Direction::Vertical));
+        assert!(ws.prune_dangling_references().is_empty());
+
+        // Remove the split behind the back of the workspace
+        ws.root_area = Some(Area::Container(Container::new(Dock::new(DockHandle(1), "source"), Rect::default())));
+        ws.take_events();
+        assert_eq!(ws.prune_dangling_references(), vec![
+            DanglingReference::Focus(DockHandle(2)),
+            DanglingReference::Drag(DragTarget::SplitSizer(SplitHandle(1), 0, Direction::Vertical)),
+        ]);
+        assert_eq!(ws.focused, None);
+        assert_eq!(ws.take_events(), vec![LayoutEvent::Drag(DragSession::End)]);
+
+        ws.focused = Some(DockHandle(3));
+        assert!(ws.prune_dangling_references().is_empty());
+        assert_eq!(ws.focused, Some(DockHandle(3)));
+    }
+
+    #[test]
+    fn test_resizable_edges_root_edge() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "registers"));
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        let edges = ws.resizable_edges(DockHandle(1));
+        assert!(edges.right && !edges.left && !edges.top && !edges.bottom);
+
+        ws.root_edge_resize = true;
+        let edges = ws.resizable_edges(DockHandle(1));
+        assert!(edges.right && edges.left && edges.top && edges.bottom);
+
+        ws.root_edge_resize = false;
+        let edges = ws.resizable_edges(DockHandle(2));
+        assert!(edges.contains(Edge::Left) && !edges.contains(Edge::Right));
+    }
+
+    #[test]
+    fn test_swap_with_neighbor() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "registers"), 0.25);
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        let left = ws.get_rect_by_handle(DockHandle(1)).unwrap();
+        let right = ws.get_rect_by_handle(DockHandle(2)).unwrap();
+
+        assert!(ws.swap_with_neighbor(DockHandle(1), Edge::Right));
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)).unwrap(), right);
+        assert_eq!(ws.get_rect_by_handle(DockHandle(2)).unwrap(), left);
+        assert_eq!(left.width, 100.0);
+
+        assert!(!ws.swap_with_neighbor(DockHandle(1), Edge::Right));
+        assert!(!ws.swap_with_neighbor(DockHandle(1), Edge::Top));
+    }
+
+    #[test]
+    fn test_filtered_view() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "registers"), 0.25);
+        ws.stack_below(DockHandle(1), Dock::new(DockHandle(3), "memory"), 0.5);
+        ws.stack_below(DockHandle(2), Dock::new(DockHandle(4), "locals"), 0.5);
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        let state = ws.to_pretty_json();
+
+        let view = ws.filtered_view(&[DockHandle(1), DockHandle(4)]);
+        let handles: Vec<DockHandle> = view.get_docks().iter().map(|dock| dock.handle).collect();
+        assert_eq!(handles, vec![DockHandle(1), DockHandle(4)]);
+        assert_eq!(view.get_rect_by_handle(DockHandle(1)), Some(Rect::new(0.0, 0.0, 200.0, 300.0)));
+        assert_eq!(view.get_rect_by_handle(DockHandle(4)), Some(Rect::new(200.0, 0.0, 200.0, 300.0)));
+
+        assert_eq!(ws.get_docks().len(), 4);
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)), Some(Rect::new(0.0, 0.0, 100.0, 150.0)));
+        assert_eq!(ws.to_pretty_json(), state);
+
+        assert!(ws.filtered_view(&[]).root_area.is_none());
+    }
+
+    #[test]
+    fn test_compact_mode() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "registers"));
+        ws.split_by_dock_handle(Direction::Horizontal, DockHandle(2), Dock::new(DockHandle(3), "memory"));
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        ws.compact_threshold = Some(250.0);
+        assert_eq!(ws.all_sizers().len(), 2);
+
+        ws.update(Rect::new(0.0, 0.0, 200.0, 200.0));
+        assert!(ws.all_sizers().is_empty());
+        assert_eq!(ws.get_drag_target_at_pos((100.0, 150.0)), None);
+
+        ws.update(Rect::new(0.0, 0.0, 300.0, 200.0));
+        let sizers = ws.all_sizers();
+        assert_eq!(sizers.len(), 1);
+        assert_eq!(sizers[0].direction, Direction::Vertical);
+        assert_eq!(ws.get_drag_target_at_pos((150.0, 150.0)), Some(DragTarget::SplitSizer(sizers[0].handle, 0, Direction::Vertical)));
+
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        assert_eq!(ws.all_sizers().len(), 2);
+    }
+
+    #[test]
+    fn test_resize_reports_change() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "registers"));
+
+        assert!(!ws.resize(Rect::new(0.0, 0.0, 400.0, 300.0)));
+        assert!(ws.resize(Rect::new(0.0, 0.0, 500.0, 300.0)));
+        assert!(!ws.resize(Rect::new(0.0, 0.0, 500.0, 300.0)));
+    }
+
+    #[test]
+    fn test_root_edge_resize() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        assert!(ws.get_drag_target_at_pos((400.0, 150.0)).is_none());
+
+        ws.root_edge_resize = true;
+        let edge = match ws.get_drag_target_at_pos((401.0, 150.0)) {
+            Some(DragTarget::RootEdge(edge)) => edge,
+            other => panic!("Expected root edge, got {:?}", other),
+        };
+        assert_eq!(edge, Edge::Right);
+
+        ws.drag_root_edge(edge, (-50.0, 0.0));
+        let rect = ws.get_rect_by_handle(DockHandle(1)).unwrap();
+        assert_eq!(rect.x as i32, 0);
+        assert_eq!(rect.width as i32, 450);
+        assert_eq!(rect.height as i32, 300);
+    }
+
+    fn collect_split_handles(area: &Area, target: &mut Vec<SplitHandle>) {
+        if let Area::Split(s) = area {
+            target.push(s.handle);
+            for child in &s.children {
+                collect_split_handles(child, target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_beside() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        let mut other = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        other.initialize(Dock::new(DockHandle(1), "registers"));
+
+        assert_eq!(ws.merge_beside(other, Direction::Horizontal, 0.5), vec![(DockHandle(1), DockHandle(2))]);
+        let root = match ws.root_area {
+            Some(Area::Split(ref s)) => s,
+            _ => panic!("Expected split at root"),
+        };
+        assert_eq!(root.direction, Direction::Horizontal);
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].find_container_by_dock_handle(DockHandle(1)).unwrap().docks[0].plugin_name, "source");
+        assert_eq!(root.children[1].find_container_by_dock_handle(DockHandle(2)).unwrap().docks[0].plugin_name, "registers");
+        assert_eq!(ws.get_rect_by_handle(DockHandle(2)).unwrap().y as i32, 150);
+    }
+
+    #[test]
+    fn test_merge_beside_nested() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        let mut other = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        other.initialize(Dock::new(DockHandle(2), "test"));
+        other.split_by_dock_handle(Direction::Horizontal, DockHandle(2), Dock::new(DockHandle(3), "test"));
+
+        other.add_floating(Dock::new(DockHandle(4), "test"), Rect::new(10.0, 10.0, 50.0, 50.0));
+        ws.add_floating(Dock::new(DockHandle(5), "test"), Rect::new(10.0, 10.0, 50.0, 50.0));
+
+        assert!(ws.merge_beside(other, Direction::Vertical, 0.5).is_empty());
+        assert_eq!(ws.floating.len(), 2);
+        assert_eq!(ws.node_counts(), NodeCounts { splits: 2, containers: 5, docks: 5, floating: 2 });
+
+        let root = match ws.root_area {
+            Some(Area::Split(ref s)) => s,
+            _ => panic!("Expected split at root"),
+        };
+        assert_eq!(root.direction, Direction::Vertical);
+        assert_eq!(root.children.len(), 2);
+        assert!(root.children[0].find_container_by_dock_handle(DockHandle(1)).is_some());
+        assert!(root.children[1].find_container_by_dock_handle(DockHandle(2)).is_some());
+        assert!(root.children[1].find_container_by_dock_handle(DockHandle(3)).is_some());
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)).unwrap().width as i32, 200);
+
+        let mut handles = Vec::new();
+        collect_split_handles(ws.root_area.as_ref().unwrap(), &mut handles);
+        assert_eq!(handles.len(), 2);
+        assert!(handles[0] != handles[1]);
+    }
+
+    #[test]
+    fn test_validate_drop() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "test"));
+
+        assert_eq!(ws.validate_drop(DockHandle(1), &DropTarget::Dock(DockHandle(2))), Ok(()));
+        assert_eq!(ws.validate_drop(DockHandle(1), &DropTarget::Dock(DockHandle(1))), Err(DropRejection::SelfDrop));
+        assert_eq!(ws.validate_drop(DockHandle(1), &DropTarget::Dock(DockHandle(7))), Err(DropRejection::TargetNotFound));
+
+        if let Some((split, index)) = ws.root_area.as_mut().unwrap().find_split_by_dock_handle(DockHandle(2)) {
+            if let Area::Container(ref mut c) = split.children[index] {
+                c.accepts_drops = false;
+            }
+        }
+        assert_eq!(ws.validate_drop(DockHandle(1), &DropTarget::Dock(DockHandle(2))), Err(DropRejection::TargetLocked));
+    }
+
+    #[test]
+    fn test_validate_drop_min_children() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5);
+        ws.stack_right(DockHandle(2), Dock::new(DockHandle(3), "test"), 0.5);
+        if let Some(Area::Split(ref mut s)) = ws.root_area {
+            s.min_children = Some(3);
+        }
+        let target = DropTarget::Dock(DockHandle(3));
+        assert_eq!(ws.validate_drop(DockHandle(1), &target), Err(DropRejection::BelowMinChildren));
+        assert!(ws.commit_drop(DockHandle(1), &target).is_err());
+
+        // Container keeps existing while it has other tabs
+        if let Some((split, index)) = ws.root_area.as_mut().unwrap().find_split_by_dock_handle(DockHandle(1)) {
+            if let Area::Container(ref mut c) = split.children[index] {
+                c.docks.push(Dock::new(DockHandle(4), "test"));
+            }
+        }
+        assert_eq!(ws.validate_drop(DockHandle(1), &target), Ok(()));
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        let docks = vec![Dock::new(DockHandle(2), "test"), Dock::new(DockHandle(3), "test"), Dock::new(DockHandle(4), "test")];
+        ws.split_evenly(Direction::Vertical, DockHandle(1), docks);
+
+        match ws.root_area {
+            Some(Area::Split(ref s)) => {
+                assert_eq!(s.ratios, vec![0.25, 0.5, 0.75, 1.0]);
+                assert_eq!(s.children.len(), 4);
+            },
+            _ => panic!("Expected split at root"),
+        }
+        for i in 0..4 {
+            let rect = ws.get_rect_by_handle(DockHandle(i + 1)).unwrap();
+            assert_eq!(rect.x as i32, i as i32 * 100);
+            assert_eq!(rect.width as i32, 100);
+        }
+    }
+
+    #[test]
+    fn test_wrap_child_in_split() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "test"));
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        ws.mark_saved();
+
+        assert!(!ws.wrap_child_in_split(DockHandle(9), Direction::Horizontal));
+        assert!(!ws.is_dirty());
+        assert!(ws.wrap_child_in_split(DockHandle(2), Direction::Horizontal));
+        assert!(ws.is_dirty());
+        assert_eq!(ws.get_rect_by_handle(DockHandle(2)), Some(Rect::new(200.0, 0.0, 200.0, 300.0)));
+
+        ws.split_by_dock_handle(Direction::Horizontal, DockHandle(2), Dock::new(DockHandle(3), "test"));
+        ws.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        let wrapper = ws.root_area.as_ref().unwrap().split(SplitHandle(2)).unwrap();
+        assert_eq!(wrapper.children.len(), 2);
+        assert_eq!(ws.node_counts().splits, 2);
+        // The sibling shares the space of the wrapped container, nothing else moves
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)), Some(Rect::new(0.0, 0.0, 200.0, 300.0)));
+        assert_eq!(ws.get_rect_by_handle(DockHandle(3)), Some(Rect::new(200.0, 0.0, 200.0, 150.0)));
+        assert_eq!(ws.get_rect_by_handle(DockHandle(2)), Some(Rect::new(200.0, 150.0, 200.0, 150.0)));
+    }
+
+    #[test]
+    fn test_split_evenly_into_parent() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "test"));
+        ws.mark_saved();
+
+        // Missing target doesn't change anything or use up a split handle
+        ws.split_evenly(Direction::Vertical, DockHandle(9), vec![Dock::new(DockHandle(3), "test")]);
+        assert!(!ws.is_dirty());
+
+        ws.split_evenly(Direction::Vertical, DockHandle(2), vec![Dock::new(DockHandle(3), "test"), Dock::new(DockHandle(4), "test")]);
+        assert!(ws.is_dirty());
+        assert_eq!(ws.node_counts().splits, 1);
+        for i in 0..3 {
+            let rect = ws.get_rect_by_handle(DockHandle(i + 2)).unwrap();
+            assert!((rect.x - (200.0 + i as f32 * 200.0 / 3.0)).abs() < 0.01);
+            assert!((rect.width - 200.0 / 3.0).abs() < 0.01);
+        }
+
+        ws.split_evenly(Direction::Horizontal, DockHandle(1), vec![Dock::new(DockHandle(5), "test")]);
+        assert!(ws.root_area.as_ref().unwrap().split(SplitHandle(2)).is_some());
+    }
+
+    #[test]
+    fn test_swap_docks() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "first"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "second"));
+        ws.split_by_dock_handle(Direction::Horizontal, DockHandle(2), Dock::new(DockHandle(3), "third"));
+        ws.drag_sizer(SplitHandle(1), 0, (-100.0, 0.0));
+
+        let rect_1 = ws.get_rect_by_handle(DockHandle(1)).unwrap();
+        let rect_3 = ws.get_rect_by_handle(DockHandle(3)).unwrap();
+        assert!(rect_1.width != rect_3.width);
+
+        assert!(ws.swap_docks(DockHandle(1), DockHandle(3)));
+        assert_eq!(ws.get_rect_by_handle(DockHandle(3)).unwrap(), rect_1);
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)).unwrap(), rect_3);
+        assert_eq!(ws.root_area.as_ref().unwrap().dock(DockHandle(3)).unwrap().plugin_name, "third");
+
+        assert!(!ws.swap_docks(DockHandle(1), DockHandle(7)));
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)).unwrap(), rect_3);
+    }
+
+    #[test]
+    fn test_stack_below() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        assert!(ws.stack_below(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.75));
+        assert!(ws.stack_below(DockHandle(1), Dock::new(DockHandle(3), "test"), 0.5));
+        assert!(!ws.stack_below(DockHandle(7), Dock::new(DockHandle(4), "test"), 0.5));
+
+        match ws.root_area {
+            Some(Area::Split(ref s)) => {
+                assert_eq!(s.direction, Direction::Horizontal);
+                assert_eq!(s.children.len(), 3);
+            },
+            _ => panic!("Expected split at root"),
+        }
+        assert_eq!(ws.root_area.as_ref().unwrap().docks_in_visual_order(), vec![DockHandle(1), DockHandle(3), DockHandle(2)]);
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)).unwrap(), Rect::new(0.0, 0.0, 400.0, 112.5));
+        assert_eq!(ws.get_rect_by_handle(DockHandle(2)).unwrap(), Rect::new(0.0, 225.0, 400.0, 75.0));
+    }
+
+    #[test]
+    fn test_stack_right() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        assert!(ws.stack_below(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5));
+        assert!(ws.stack_right(DockHandle(2), Dock::new(DockHandle(3), "test"), 0.25));
+
+        let (split, index) = ws.root_area.as_mut().unwrap().find_split_by_dock_handle(DockHandle(3)).unwrap();
+        assert_eq!(split.direction, Direction::Vertical);
+        assert_eq!(index, 1);
+        assert!(split.children[0].find_container_by_dock_handle(DockHandle(2)).is_some());
+        assert_eq!(ws.get_rect_by_handle(DockHandle(3)).unwrap(), Rect::new(100.0, 150.0, 300.0, 150.0));
+    }
+
+    #[test]
+    fn test_commit_drop_after_tree_change() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5);
+        ws.stack_below(DockHandle(2), Dock::new(DockHandle(3), "test"), 0.5);
+
+        let target = ws.get_drop_target_at_pos((210.0, 160.0)).unwrap();
+        let rect_1 = ws.get_rect_by_handle(DockHandle(1)).unwrap();
+        let rect_3 = ws.get_rect_by_handle(DockHandle(3)).unwrap();
+
+        // target moves before the drop is committed
+        ws.swap_docks(DockHandle(2), DockHandle(3));
+        assert!(ws.commit_drop(DockHandle(1), &target).is_ok());
+        assert_eq!(ws.get_rect_by_handle(DockHandle(3)).unwrap(), rect_1);
+        assert_eq!(ws.get_rect_by_handle(DockHandle(1)).unwrap().width, rect_3.width);
+
+        // target is removed before the drop is committed
+        let target = ws.get_drop_target_at_pos((10.0, 10.0)).unwrap();
+        ws.delete_by_handle(DockHandle(3));
+        match ws.commit_drop(DockHandle(1), &target) {
+            Err(Error::DropRejected(DropRejection::TargetNotFound)) => (),
+            other => panic!("Expected rejected drop, got {:?}", other),
+        }
+        assert_eq!(ws.get_docks().len(), 2);
+    }
+
+    #[test]
+    fn test_save_generation() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        assert_eq!(ws.generation(), 0);
+
+        // Undo snapshots don't count as saves
+        ws.save_state();
+        assert_eq!(ws.generation(), 0);
+
+        let first = ws.save_for_disk(None);
+        assert_eq!(ws.generation(), 1);
+        assert_eq!(ws.saved_at(), None);
+        let second = ws.save_for_disk(Some(1234));
+        assert_eq!(ws.generation(), 2);
+        assert_eq!(ws.saved_at(), Some(1234));
+        assert_eq!(ws.save_state(), second);
+
+        let older = Workspace::from_state(&first);
+        let newer = Workspace::from_state(&second);
+        assert_eq!(older.generation(), 1);
+        assert_eq!(newer.saved_at(), Some(1234));
+        assert!(older.is_older_than(&newer));
+        assert!(!newer.is_older_than(&older));
+
+        // Layouts saved before generations existed start from zero
+        let legacy = second.replace("\"generation\":2,", "");
+        assert_eq!(Workspace::from_state(&legacy).generation(), 0);
+    }
+
+    #[test]
+    fn test_to_pretty_json() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.25);
+
+        let pretty = ws.to_pretty_json();
+        assert_eq!(pretty, ws.to_pretty_json());
+        assert!(pretty.find("\"floating\"").unwrap() < pretty.find("\"handle_counter\"").unwrap());
+        assert!(pretty.find("\"handle_counter\"").unwrap() < pretty.find("\"rect\"").unwrap());
+
+        let mut reloaded = Workspace::from_state(&pretty);
+        reloaded.update(Rect::new(0.0, 0.0, 400.0, 300.0));
+        assert_eq!(reloaded.save_state(), ws.save_state());
+        assert_eq!(reloaded.to_pretty_json(), pretty);
+    }
+
+    #[test]
+    fn test_floating_visible() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.add_floating(Dock::new(DockHandle(2), "float"), Rect::new(50.0, 50.0, 100.0, 100.0));
+
+        assert_eq!(ws.get_hover_dock((100.0, 100.0)), Some(DockHandle(2)));
+
+        ws.set_floating_visible(false);
+        assert_eq!(ws.get_hover_dock((100.0, 100.0)), Some(DockHandle(1)));
+        assert_eq!(ws.visible_floating().count(), 0);
+        assert_eq!(ws.floating.len(), 1);
+
+        let reloaded = Workspace::from_state(&ws.save_state());
+        assert!(!reloaded.is_floating_visible());
+        assert_eq!(reloaded.floating.len(), 1);
+        let legacy = ws.save_state().replace("\"floating_visible\":false,", "");
+        assert!(Workspace::from_state(&legacy).is_floating_visible());
+
+        ws.set_floating_visible(true);
+        assert_eq!(ws.get_hover_dock((100.0, 100.0)), Some(DockHandle(2)));
+    }
+
+    #[test]
+    fn test_dpi_scaled_sizer() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5);
+        // Sizer is at x = 200 and the band extends half the sizer width to each side
+        assert_eq!(ws.sizer_width(), SIZER_WIDTH);
+        assert!(ws.get_drag_target_at_pos((200.0 + SIZER_WIDTH * 0.75, 150.0)).is_none());
+
+        ws.dpi_scale = 2.0;
+        assert_eq!(ws.sizer_width(), SIZER_WIDTH * 2.0);
+        match ws.get_drag_target_at_pos((200.0 + SIZER_WIDTH * 0.75, 150.0)) {
+            Some(DragTarget::SplitSizer(_, 0, Direction::Vertical)) => {},
+            other => panic!("expected sizer, got {:?}", other),
+        }
+        assert!(ws.get_drag_target_at_pos((200.0 + SIZER_WIDTH * 1.25, 150.0)).is_none());
+    }
+
+    #[test]
+    fn test_dock_manifest() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "registers"), 0.5);
+        if let Some(ref mut root) = ws.root_area {
+            let (split, index) = root.find_split_by_dock_handle(DockHandle(2)).unwrap();
+            if let Area::Container(ref mut container) = split.children[index] {
+                let mut tab = Dock::new(DockHandle(3), "memory");
+                tab.plugin_data = Some(vec!["0x1000".to_owned()]);
+                container.docks.push(tab);
+            }
+        }
+        ws.add_floating(Dock::new(DockHandle(4), "console"), Rect::new(10.0, 10.0, 50.0, 50.0));
+
+        let mut manifest = ws.dock_manifest();
+        manifest.sort_by_key(|entry| entry.handle.0);
+        assert_eq!(manifest.len(), 4);
+        let names: Vec<&str> = manifest.iter().map(|entry| entry.plugin_name.as_str()).collect();
+        assert_eq!(names, vec!["source", "registers", "memory", "console"]);
+        assert_eq!(manifest[2], DockManifestEntry {
+            handle: DockHandle(3),
+            plugin_name: "memory".to_owned(),
+            plugin_data: Some(vec!["0x1000".to_owned()]),
+        });
+    }
+
+    #[test]
+    fn test_sizer_for_focus() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5);
+        ws.stack_right(DockHandle(2), Dock::new(DockHandle(3), "test"), 0.5);
+        ws.stack_below(DockHandle(2), Dock::new(DockHandle(4), "test"), 0.5);
+        let root_handle = match ws.root_area {
+            Some(Area::Split(ref s)) => s.handle,
+            _ => panic!("expected split at root"),
+        };
+
+        assert_eq!(ws.sizer_for_focus(Direction::Vertical, true), None);
+
+        ws.focused = Some(DockHandle(2));
+        assert_eq!(ws.sizer_for_focus(Direction::Vertical, true), Some((root_handle, 1)));
+        assert_eq!(ws.sizer_for_focus(Direction::Vertical, false), Some((root_handle, 0)));
+        let below = ws.sizer_for_focus(Direction::Horizontal, true).unwrap();
+        assert!(below.0 != root_handle);
+        assert_eq!(below.1, 0);
+        assert_eq!(ws.sizer_for_focus(Direction::Horizontal, false), None);
+
+        ws.focused = Some(DockHandle(3));
+        assert_eq!(ws.sizer_for_focus(Direction::Vertical, true), None);
+        assert_eq!(ws.sizer_for_focus(Direction::Vertical, false), Some((root_handle, 1)));
+    }
+
+    #[test]
+    fn test_checksum() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5);
+
+        let saved = ws.save_state_with_checksum();
+        let loaded = Workspace::from_state_checked(&saved).unwrap();
+        assert_eq!(loaded.save_state(), ws.save_state());
+
+        let corrupted = saved.replace("0.5", "0.6");
+        assert!(corrupted != saved);
+        match Workspace::from_state_checked(&corrupted) {
+            Err(Error::ChecksumMismatch) => {},
+            other => panic!("expected checksum mismatch, got {:?}", other.map(|_| ())),
+        }
+
+        let truncated = &saved[..saved.len() - 10];
+        assert!(Workspace::from_state_checked(truncated).is_err());
+
+        if let Some(Area::Split(ref mut s)) = ws.root_area {
+            s.min_children = Some(3);
+        }
+        match Workspace::from_state_checked(&ws.save_state_with_checksum()) {
+            Err(Error::InvalidChildCount(SplitHandle(1), 2)) => {},
+            other => panic!("expected InvalidChildCount, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_dirty_flag() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5);
+        // Undo snapshots don't count as saves
+        ws.save_state();
+        ws.to_pretty_json();
+        assert!(ws.is_dirty());
+        ws.mark_saved();
+        ws.take_events();
+        assert!(!ws.is_dirty());
+
+        // Operations that don't find their target or don't change anything
+        ws.delete_by_handle(DockHandle(7));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(7), Dock::new(DockHandle(3), "test"));
+        ws.simplify();
+        assert!(!ws.is_dirty());
+        assert!(ws.take_events().is_empty());
+        assert_eq!(ws.get_docks().len(), 2);
+
+        let handle = match ws.root_area {
+            Some(Area::Split(ref s)) => s.handle,
+            _ => panic!("expected split at root"),
+        };
+        // Perpendicular move doesn't change anything
+        assert!(!ws.drag_sizer(handle, 0, (0.0, 10.0)));
+        assert!(!ws.is_dirty());
+        assert!(ws.take_events().is_empty());
+
+        assert!(ws.drag_sizer(handle, 0, (10.0, 0.0)));
+        assert!(ws.is_dirty());
+        assert_eq!(ws.take_events(), vec![LayoutEvent::Changed]);
+
+        let saved = ws.save_for_disk(None);
+        assert!(!ws.is_dirty());
+        assert!(!Workspace::from_state(&saved).is_dirty());
+    }
+
+    #[test]
+    fn test_focus_follows_largest() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.3);
+        ws.stack_right(DockHandle(2), Dock::new(DockHandle(3), "test"), 0.5);
+        let handle = match ws.root_area {
+            Some(Area::Split(ref s)) => s.handle,
+            _ => panic!("expected split at root"),
+        };
+        ws.focused = Some(DockHandle(1));
+
+        // Policy is opt-in
+        ws.drag_sizer(handle, 0, (200.0, 0.0));
+        assert_eq!(ws.focused, Some(DockHandle(1)));
+
+        ws.drag_sizer(handle, 0, (-200.0, 0.0));
+        ws.focus_follows_largest = Some(20.0);
+        ws.drag_sizer(handle, 0, (50.0, 0.0));
+        assert_eq!(ws.focused, Some(DockHandle(1)));
+
+        // Dock 1 shrinks to near zero width and dock 2 becomes the largest
+        ws.drag_sizer(handle, 0, (200.0, 0.0));
+        assert!(ws.get_rect_by_handle(DockHandle(1)).unwrap().width < 20.0);
+        assert_eq!(ws.focused, Some(DockHandle(2)));
+    }
+
+    #[test]
+    fn test_drag_session_events() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "test"));
+        ws.stack_right(DockHandle(1), Dock::new(DockHandle(2), "test"), 0.5);
+        let handle = match ws.root_area {
+            Some(Area::Split(ref s)) => s.handle,
+            _ => panic!("expected split at root"),
+        };
+        ws.take_events();
+
+        let target = ws.get_drag_target_at_pos((200.0, 150.0)).unwrap();
+        assert_eq!(target, DragTarget::SplitSizer(handle, 0, Direction::Vertical));
+        ws.begin_drag(target);
+        ws.drag_sizer(handle, 0, (-40.0, 0.0));
+        ws.drag_sizer(handle, 0, (0.0, 5.0));
+        ws.drag_sizer(handle, 0, (20.0, 0.0));
+        ws.end_drag();
+        ws.end_drag();
+
+        let drag_events: Vec<DragSession> = ws.take_events().into_iter()
+            .filter_map(|event| match event {
+                LayoutEvent::Drag(session) => Some(session),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(drag_events, vec![
+            DragSession::Begin(target),
+            DragSession::Update { ratio: 0.6, delta: (-40.0, 0.0) },
+            DragSession::Update { ratio: 0.55, delta: (20.0, 0.0) },
+            DragSession::End,
+        ]);
+
+        // Without a session only change events are sent
+        ws.drag_sizer(handle, 0, (20.0, 0.0));
+        assert_eq!(ws.take_events(), vec![LayoutEvent::Changed]);
+    }
+
+    #[test]
+    fn test_paste_subtree() {
+        let mut source = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        source.initialize(Dock::new(DockHandle(1), "source"));
+        source.stack_right(DockHandle(1), Dock::new(DockHandle(2), "memory"), 0.5);
+        source.stack_below(DockHandle(2), Dock::new(DockHandle(3), "registers"), 0.25);
+        let subtree_handle = source.root_area.as_ref().unwrap().split_path_to_dock(DockHandle(3)).unwrap()[1];
+        let data = source.root_area.as_ref().unwrap().serialize_subtree(subtree_handle).unwrap();
+        assert!(source.root_area.as_ref().unwrap().serialize_subtree(SplitHandle(99)).is_none());
+
+        let mut target = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        target.initialize(Dock::new(DockHandle(10), "console"));
+        target.stack_right(DockHandle(10), Dock::new(DockHandle(11), "locals"), 0.5);
+        target.paste_subtree(&data, &DropTarget::Dock(DockHandle(10)), Edge::Left).unwrap();
+
+        // Pasted group is to the left of dock 10 and keeps its inner layout
+        let mut handles = Vec::new();
+        collect_split_handles(target.root_area.as_ref().unwrap(), &mut handles);
+        let pasted = target.root_area.as_ref().unwrap().common_ancestor(DockHandle(2), DockHandle(3)).unwrap();
+        assert_eq!(handles.len(), 2);
+        for handle in &handles {
+            assert_eq!(handles.iter().filter(|&h| h == handle).count(), 1);
+        }
+        let split = target.root_area.as_ref().unwrap().split(pasted).unwrap();
+        assert_eq!(split.direction, Direction::Horizontal);
+        assert_eq!(split.ratios, vec![0.25, 1.0]);
+        let pasted_rect = split.rect;
+        let dock_rect = target.get_rect_by_handle(DockHandle(10)).unwrap();
+        assert!(pasted_rect.x + pasted_rect.width <= dock_rect.x + 0.001);
+        assert_eq!(target.get_docks().len(), 4);
+
+        match target.paste_subtree(&data, &DropTarget::Dock(DockHandle(11)), Edge::Top) {
+            Err(Error::DockExists(DockHandle(2))) => {},
+            other => panic!("expected DockExists, got {:?}", other),
+        }
     }
 }
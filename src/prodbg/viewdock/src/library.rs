@@ -0,0 +1,136 @@
+extern crate serde;
+extern crate serde_json;
+
+use std::collections::BTreeMap;
+use {Workspace, Error, ResultView};
+
+/// Named workspace layouts ("Debug", "Edit", ...) the user can switch between. The library is
+/// serializable on its own so it can be stored next to the workspace state.
+#[derive(Debug, Default, Clone)]
+pub struct LayoutLibrary {
+    layouts: BTreeMap<String, String>,
+}
+
+impl LayoutLibrary {
+    pub fn new() -> LayoutLibrary {
+        LayoutLibrary::default()
+    }
+
+    /// Stores current layout of `ws` as `name`. Layout already stored with the name is replaced.
+    pub fn save(&mut self, name: &str, ws: &Workspace) {
+        let state = serde_json::to_string(ws).unwrap();
+        self.layouts.insert(name.to_owned(), state);
+    }
+
+    /// Replaces tiled and floating layout of `ws` with the one stored as `name` and recalculates
+    /// rects for the current size of the workspace. `ws` isn't changed if the stored layout
+    /// doesn't pass `Workspace::validate`.
+    pub fn load(&self, name: &str, ws: &mut Workspace) -> ResultView<()> {
+        let state = match self.layouts.get(name) {
+            Some(state) => state,
+            None => return Err(Error::LayoutNotFound(name.to_owned())),
+        };
+        let stored: Workspace = serde_json::from_str(state).map_err(|e| Error::InvalidLayout(format!("{}", e)))?;
+        stored.validate()?;
+        ws.root_area = stored.root_area;
+        ws.floating = stored.floating;
+        ws.floating_visible = stored.floating_visible;
+        ws.handle_counter.0 = ws.handle_counter.0.max(stored.handle_counter.0);
+        let rect = ws.rect;
+        ws.update(rect);
+        ws.mark_dirty();
+        Ok(())
+    }
+
+    /// Removes layout stored as `name`. Returns false if there wasn't one.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.layouts.remove(name).is_some()
+    }
+
+    /// Returns names of stored layouts in sorted order
+    pub fn list(&self) -> Vec<&str> {
+        self.layouts.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+// Serialization
+
+impl serde::ser::Serialize for LayoutLibrary {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: serde::ser::Serializer {
+        self.layouts.serialize(serializer)
+    }
+}
+
+// Deserialization
+
+impl serde::Deserialize for LayoutLibrary {
+    fn deserialize<D>(deserializer: &mut D) -> Result<LayoutLibrary, D::Error> where D: serde::de::Deserializer {
+        let layouts = BTreeMap::deserialize(deserializer)?;
+        Ok(LayoutLibrary { layouts })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate serde_json;
+    use {LayoutLibrary, Workspace, Rect, Dock, DockHandle, Direction, Area, Error, SplitHandle};
+
+    #[test]
+    fn test_layout_library() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "registers"));
+        let mut library = LayoutLibrary::new();
+        library.save("Debug", &ws);
+
+        ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        library.save("Minimal", &ws);
+        assert_eq!(library.list(), vec!["Debug", "Minimal"]);
+
+        let serialized = serde_json::to_string(&library).unwrap();
+        let library: LayoutLibrary = serde_json::from_str(&serialized).unwrap();
+
+        ws.update(Rect::new(0.0, 0.0, 800.0, 600.0));
+        library.load("Debug", &mut ws).unwrap();
+        match ws.root_area {
+            Some(Area::Split(ref s)) => {
+                assert_eq!(s.children.len(), 2);
+                assert_eq!(s.direction, Direction::Vertical);
+                assert_eq!(s.rect, Rect::new(0.0, 0.0, 800.0, 600.0));
+            },
+            _ => panic!("Expected split"),
+        }
+        assert_eq!(ws.get_docks().len(), 2);
+
+        library.load("Minimal", &mut ws).unwrap();
+        match ws.root_area {
+            Some(Area::Container(ref c)) => {
+                assert_eq!(c.docks[0].handle, DockHandle(1));
+                assert_eq!(c.rect, Rect::new(0.0, 0.0, 800.0, 600.0));
+            },
+            _ => panic!("Expected container"),
+        }
+        assert!(library.load("Edit", &mut ws).is_err());
+    }
+
+    #[test]
+    fn test_load_invalid_child_count() {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        ws.initialize(Dock::new(DockHandle(1), "source"));
+        ws.split_by_dock_handle(Direction::Vertical, DockHandle(1), Dock::new(DockHandle(2), "registers"));
+        if let Some(Area::Split(ref mut s)) = ws.root_area {
+            s.max_children = Some(1);
+        }
+        let mut library = LayoutLibrary::new();
+        library.save("Broken", &ws);
+
+        let mut target = Workspace::new(Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        target.initialize(Dock::new(DockHandle(3), "memory"));
+        match library.load("Broken", &mut target) {
+            Err(Error::InvalidChildCount(SplitHandle(1), 2)) => {},
+            other => panic!("expected InvalidChildCount, got {:?}", other),
+        }
+        assert_eq!(target.get_docks()[0].handle, DockHandle(3));
+    }
+}
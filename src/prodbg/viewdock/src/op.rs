@@ -0,0 +1,23 @@
+use area::{DropTarget, SplitHandle};
+use dock::{Dock, DockHandle};
+use rect::Direction;
+
+/// Single layout operation for `Workspace::apply_batch`. Each maps to the workspace method of
+/// the same name.
+#[derive(Debug, Clone)]
+pub enum LayoutOp {
+    /// `Workspace::split_by_dock_handle`
+    Split { direction: Direction, target: DockHandle, dock: Dock },
+    /// `Workspace::stack_right`
+    StackRight { target: DockHandle, dock: Dock, ratio: f32 },
+    /// `Workspace::stack_below`
+    StackBelow { target: DockHandle, dock: Dock, ratio: f32 },
+    /// `Workspace::drag_sizer`
+    DragSizer { handle: SplitHandle, index: usize, delta: (f32, f32) },
+    /// `Workspace::commit_drop`
+    Drop { dragging: DockHandle, target: DropTarget },
+    /// `Workspace::swap_docks`
+    Swap(DockHandle, DockHandle),
+    /// `Workspace::delete_by_handle`
+    Delete(DockHandle),
+}
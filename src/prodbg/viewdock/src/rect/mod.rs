@@ -1,13 +1,57 @@
 mod serialize;
 
+use area::{MIN_RATIO, MAX_RATIO};
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Direction {
     Vertical,
     Horizontal,
 }
 
+/// Edges of a rectangle
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Set of edges of a rectangle
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct EdgeSet {
+    pub left: bool,
+    pub right: bool,
+    pub top: bool,
+    pub bottom: bool,
+}
+
+impl EdgeSet {
+    pub fn contains(&self, edge: Edge) -> bool {
+        match edge {
+            Edge::Left => self.left,
+            Edge::Right => self.right,
+            Edge::Top => self.top,
+            Edge::Bottom => self.bottom,
+        }
+    }
+
+    pub fn insert(&mut self, edge: Edge) {
+        match edge {
+            Edge::Left => self.left = true,
+            Edge::Right => self.right = true,
+            Edge::Top => self.top = true,
+            Edge::Bottom => self.bottom = true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(self.left || self.right || self.top || self.bottom)
+    }
+}
+
 /// Data structure for rectangles
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
 pub struct Rect {
     pub x: f32,
     pub y: f32,
@@ -18,13 +62,58 @@ pub struct Rect {
 impl Rect {
     pub fn new(x: f32, y: f32, width: f32, height: f32) -> Rect {
         Rect {
-            x: x,
-            y: y,
-            width: width,
-            height: height
+            x,
+            y,
+            width,
+            height
         }
     }
 
+    /// Returns true if all components of the rects differ less than epsilon
+    pub fn approx_eq(&self, other: &Rect, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon &&
+        (self.y - other.y).abs() <= epsilon &&
+        (self.width - other.width).abs() <= epsilon &&
+        (self.height - other.height).abs() <= epsilon
+    }
+
+    /// Returns fraction of the rect along `direction` that is `pixels` long, clamped to
+    /// [`MIN_RATIO`, `MAX_RATIO`] so neither side of a split made with it collapses. Vertical is
+    /// the width and Horizontal the height like with splits.
+    pub fn ratio_for_pixels(&self, direction: Direction, pixels: f32) -> f32 {
+        let length = match direction {
+            Direction::Vertical => self.width,
+            Direction::Horizontal => self.height,
+        };
+        if length <= 0.0 {
+            return MIN_RATIO;
+        }
+        (pixels / length).clamp(MIN_RATIO, MAX_RATIO)
+    }
+
+    /// Distance from the point to the closest point of the rect. Zero if point is inside
+    pub fn distance_to_point(&self, pos: (f32, f32)) -> f32 {
+        let dx = (self.x - pos.0).max(pos.0 - (self.x + self.width)).max(0.0);
+        let dy = (self.y - pos.1).max(pos.1 - (self.y + self.height)).max(0.0);
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Returns true if the rects overlap. Rects that only touch don't overlap
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.x + other.width &&
+        other.x < self.x + self.width &&
+        self.y < other.y + other.height &&
+        other.y < self.y + self.height
+    }
+
+    /// Returns true if `other` is completely inside of this rect
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.x <= other.x &&
+        self.y <= other.y &&
+        other.x + other.width <= self.x + self.width &&
+        other.y + other.height <= self.y + self.height
+    }
+
     pub fn point_is_inside(&self, pos: (f32, f32)) -> bool {
         let (x, y) = pos;
         return
@@ -34,6 +123,16 @@ impl Rect {
             self.y + self.height >= y;
     }
 
+    /// Returns band of given width centered on the edge of the rect
+    pub fn area_around_edge(&self, edge: Edge, width: f32) -> Rect {
+        match edge {
+            Edge::Left => Rect::new(self.x - width / 2.0, self.y, width, self.height),
+            Edge::Right => Rect::new(self.x + self.width - width / 2.0, self.y, width, self.height),
+            Edge::Top => Rect::new(self.x, self.y - width / 2.0, self.width, width),
+            Edge::Bottom => Rect::new(self.x, self.y + self.height - width / 2.0, self.width, width),
+        }
+    }
+
     pub fn area_around_splits(&self, direction: Direction, ratios: &[f32], width: f32) -> Vec<Rect> {
         match direction {
             Direction::Horizontal => {
@@ -58,6 +157,20 @@ impl Rect {
         }
     }
 
+    /// Places rects with given sizes one after another starting `offset` pixels from the start
+    /// of this rect. Rects may extend outside of this rect.
+    pub fn split_by_sizes(&self, direction: Direction, offset: f32, sizes: &[f32]) -> Vec<Rect> {
+        let mut pos = offset;
+        sizes.iter().map(|&size| {
+            let res = match direction {
+                Direction::Horizontal => Rect::new(self.x, self.y + pos, self.width, size),
+                Direction::Vertical => Rect::new(self.x + pos, self.y, size, self.height),
+            };
+            pos += size;
+            res
+        }).collect()
+    }
+
     pub fn split_horizontally(rect: &Rect, ratios: &[f32]) -> Vec<Rect> {
         let mut prev_height = 0.0;
         return ratios.iter().map(|ratio| {
@@ -85,6 +198,7 @@ impl Rect {
 mod test {
     extern crate serde_json;
     use {Rect, Direction};
+    use area::{MIN_RATIO, MAX_RATIO};
 
     fn check_range(inv: f32, value: f32, delta: f32) -> bool {
         (inv - value).abs() < delta
@@ -197,4 +311,14 @@ mod test {
 //        assert_eq!(Rect::is_inside((11.0, 61.0), rect_horz), true);
 //    }
 
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ratio_for_pixels() {
+        let rect = Rect::new(50.0, 0.0, 1000.0, 400.0);
+        assert_eq!(rect.ratio_for_pixels(Direction::Vertical, 200.0), 0.2);
+        assert_eq!(rect.ratio_for_pixels(Direction::Horizontal, 200.0), 0.5);
+        assert_eq!(rect.ratio_for_pixels(Direction::Vertical, 2000.0), MAX_RATIO);
+        assert_eq!(rect.ratio_for_pixels(Direction::Vertical, -10.0), MIN_RATIO);
+        assert_eq!(Rect::default().ratio_for_pixels(Direction::Vertical, 10.0), MIN_RATIO);
+    }
+}
@@ -75,7 +75,7 @@ impl serde::de::Visitor for RectVisitor {
 
         try!(visitor.end());
 
-        Ok(Rect{ x: x, y: y, width: width, height: height })
+        Ok(Rect{ x, y, width, height })
     }
 }
 
@@ -16,10 +16,14 @@ struct WorkspaceMapVisitor<'a> {
 
 impl<'a> serde::ser::MapVisitor for WorkspaceMapVisitor<'a> {
     fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error> where S: serde::Serializer {
-        try!(serializer.serialize_struct_elt("root_area", &self.value.root_area));
-        try!(serializer.serialize_struct_elt("rect", &self.value.rect));
-        try!(serializer.serialize_struct_elt("window_border", &self.value.window_border));
-        try!(serializer.serialize_struct_elt("handle_counter", &self.value.handle_counter));
+        serializer.serialize_struct_elt("root_area", &self.value.root_area)?;
+        serializer.serialize_struct_elt("floating", &self.value.floating)?;
+        serializer.serialize_struct_elt("floating_visible", self.value.floating_visible)?;
+        serializer.serialize_struct_elt("rect", &self.value.rect)?;
+        serializer.serialize_struct_elt("window_border", &self.value.window_border)?;
+        serializer.serialize_struct_elt("handle_counter", &self.value.handle_counter)?;
+        serializer.serialize_struct_elt("generation", self.value.generation)?;
+        serializer.serialize_struct_elt("saved_at", self.value.saved_at)?;
         Ok(None)
     }
 }
@@ -28,7 +32,7 @@ impl<'a> serde::ser::MapVisitor for WorkspaceMapVisitor<'a> {
 
 impl serde::Deserialize for Workspace {
     fn deserialize<D>(deserializer: &mut D) -> Result<Workspace, D::Error> where D: serde::de::Deserializer {
-        static FIELDS: &'static [&'static str] = &["root_area", "rect", "window_border", "handle_counter"];
+        static FIELDS: &[&str] = &["root_area", "floating", "floating_visible", "rect", "window_border", "handle_counter", "generation", "saved_at"];
         deserializer.deserialize_struct("Workspace", FIELDS, WorkspaceVisitor)
     }
 }
@@ -40,56 +44,82 @@ impl serde::de::Visitor for WorkspaceVisitor {
 
     fn visit_map<V>(&mut self, mut visitor: V) -> Result<Workspace, V::Error> where V: serde::de::MapVisitor {
         let mut root_area = None;
+        let mut floating = None;
+        let mut floating_visible = None;
         let mut rect = None;
         let mut window_border = None;
         let mut handle_counter = None;
+        let mut generation = None;
+        let mut saved_at = None;
 
         loop {
-            match try!(visitor.visit_key()) {
-                Some(WorkspaceField::RootArea) => { root_area = Some(try!(visitor.visit_value())); }
-                Some(WorkspaceField::Rect) => { rect = Some(try!(visitor.visit_value())); }
-                Some(WorkspaceField::WindowBorder) => { window_border = Some(try!(visitor.visit_value())); }
-                Some(WorkspaceField::HandleCounter) => { handle_counter = Some(try!(visitor.visit_value())); }
+            match visitor.visit_key()? {
+                Some(WorkspaceField::RootArea) => { root_area = Some(visitor.visit_value()?); }
+                Some(WorkspaceField::Floating) => { floating = Some(visitor.visit_value()?); }
+                Some(WorkspaceField::FloatingVisible) => { floating_visible = Some(visitor.visit_value()?); }
+                Some(WorkspaceField::Rect) => { rect = Some(visitor.visit_value()?); }
+                Some(WorkspaceField::WindowBorder) => { window_border = Some(visitor.visit_value()?); }
+                Some(WorkspaceField::HandleCounter) => { handle_counter = Some(visitor.visit_value()?); }
+                Some(WorkspaceField::Generation) => { generation = Some(visitor.visit_value()?); }
+                Some(WorkspaceField::SavedAt) => { saved_at = visitor.visit_value()?; }
                 None => { break; }
             }
         }
 
         let root_area = match root_area {
             Some(root_area) => root_area,
-            None => try!(visitor.missing_field("root_area")),
+            None => visitor.missing_field("root_area")?,
         };
 
+        let floating = floating.unwrap_or(Vec::new());
+
         let rect = match rect {
             Some(rect) => rect,
-            None => try!(visitor.missing_field("rect")),
+            None => visitor.missing_field("rect")?,
         };
 
         let window_border = match window_border {
             Some(window_border) => window_border,
-            None => try!(visitor.missing_field("window_border")),
+            None => visitor.missing_field("window_border")?,
         };
 
         let handle_counter = match handle_counter {
             Some(handle_counter) => handle_counter,
-            None => try!(visitor.missing_field("handle_counter")),
+            None => visitor.missing_field("handle_counter")?,
         };
 
-        try!(visitor.end());
+        visitor.end()?;
 
         Ok(Workspace {
-            root_area: root_area,
-            rect: rect,
-            window_border: window_border,
-            handle_counter: handle_counter,
+            root_area,
+            floating,
+            rect,
+            window_border,
+            root_edge_resize: false,
+            floating_visible: floating_visible.unwrap_or(true),
+            dpi_scale: 1.0,
+            compact_threshold: None,
+            focused: None,
+            focus_follows_largest: None,
+            dirty: false,
+            events: Vec::new(),
+            drag: None,
+            handle_counter,
+            generation: generation.unwrap_or(0),
+            saved_at,
         })
     }
 }
 
 enum WorkspaceField {
     RootArea,
+    Floating,
+    FloatingVisible,
     Rect,
     WindowBorder,
     HandleCounter,
+    Generation,
+    SavedAt,
 }
 
 impl serde::Deserialize for WorkspaceField  {
@@ -103,10 +133,14 @@ impl serde::Deserialize for WorkspaceField  {
                 where E: serde::de::Error {
                     match value {
                         "root_area" => Ok(WorkspaceField::RootArea),
+                        "floating" => Ok(WorkspaceField::Floating),
+                        "floating_visible" => Ok(WorkspaceField::FloatingVisible),
                         "rect" => Ok(WorkspaceField::Rect),
                         "window_border" => Ok(WorkspaceField::WindowBorder),
                         "handle_counter" => Ok(WorkspaceField::HandleCounter),
-                        _ => Err(serde::de::Error::custom("expected root_area,rect,window_border or handle_counter")),
+                        "generation" => Ok(WorkspaceField::Generation),
+                        "saved_at" => Ok(WorkspaceField::SavedAt),
+                        _ => Err(serde::de::Error::custom("expected root_area,floating,floating_visible,rect,window_border,handle_counter,generation or saved_at")),
                     }
                 }
         }